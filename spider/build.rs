@@ -0,0 +1,19 @@
+fn main() {
+    #[cfg(feature = "grpc")]
+    {
+        // the vendored protoc unless the one of the system is set
+        if std::env::var_os("PROTOC").is_none() {
+            match protoc_bin_vendored::protoc_bin_path() {
+                Ok(protoc) => std::env::set_var("PROTOC", protoc),
+                _ => (),
+            }
+        }
+
+        println!("cargo:rerun-if-changed=proto/spider.proto");
+
+        tonic_build::configure()
+            .build_client(false)
+            .compile(&["proto/spider.proto"], &["proto"])
+            .expect("failed to compile the gRPC proto");
+    }
+}
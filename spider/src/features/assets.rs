@@ -0,0 +1,266 @@
+use crate::configuration::AssetDownload;
+use crate::features::diff::get_digest;
+use crate::features::graph::escape_csv;
+use crate::utils::{get_body_stream, log, send_request, ContentEncoding};
+use hashbrown::HashSet;
+use reqwest::header::CONTENT_TYPE;
+use reqwest::Client;
+use std::io::Write;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use url::Url;
+
+/// The name of the manifest of the assets in the directory.
+pub const MANIFEST_FILE: &str = "manifest.csv";
+
+/// An asset downloaded.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct AssetEntry {
+    /// The url of the asset.
+    pub url: String,
+    /// The path of the file written.
+    pub path: PathBuf,
+    /// The content type of the response of the asset.
+    pub content_type: String,
+    /// The bytes of the asset.
+    pub size: usize,
+    /// The url of the page linking the asset first.
+    pub page_url: String,
+}
+
+/// The assets downloaded shared across the tasks of the crawl.
+#[derive(Debug, Default)]
+pub struct AssetStore {
+    /// The assets to download.
+    download: AssetDownload,
+    /// The urls of the assets found.
+    urls: Mutex<HashSet<String>>,
+    /// The assets written in the order of their downloads.
+    entries: Mutex<Vec<AssetEntry>>,
+}
+
+impl AssetStore {
+    /// Establish a new empty asset store.
+    pub fn new(download: AssetDownload) -> Self {
+        Self {
+            download,
+            ..Default::default()
+        }
+    }
+
+    /// Get the assets to download.
+    pub fn get_download(&self) -> &AssetDownload {
+        &self.download
+    }
+
+    /// The url is an asset to download by the extensions of the configuration. The urls without an
+    /// extension are assets when the extensions are empty.
+    pub fn is_asset(&self, url: &Url) -> bool {
+        if url.scheme() != "http" && url.scheme() != "https" {
+            return false;
+        }
+
+        match get_extension(url) {
+            Some(extension) => {
+                self.download.extensions.is_empty()
+                    || self
+                        .download
+                        .extensions
+                        .iter()
+                        .any(|e| e.trim_start_matches('.').eq_ignore_ascii_case(extension))
+            }
+            _ => self.download.extensions.is_empty(),
+        }
+    }
+
+    /// Record the url of an asset found. Returns `true` when the url was not found before.
+    pub fn insert_url(&self, url: &str) -> bool {
+        match self.urls.lock() {
+            Ok(mut urls) => urls.insert(url.into()),
+            _ => false,
+        }
+    }
+
+    /// Get the path of the file of the asset in the directory.
+    pub fn get_path(&self, url: &Url) -> PathBuf {
+        let mut path = self.download.dir.join(url.host_str().unwrap_or_default());
+        let mut segments: Vec<&str> = url
+            .path_segments()
+            .map(|segments| {
+                segments
+                    .filter(|segment| !segment.is_empty() && *segment != "." && *segment != "..")
+                    .collect()
+            })
+            .unwrap_or_default();
+        let name = match segments.pop() {
+            Some(name) if !url.path().ends_with('/') => name.to_string(),
+            Some(name) => {
+                segments.push(name);
+                "index".into()
+            }
+            _ => "index".into(),
+        };
+        let name = match url.query() {
+            Some(query) => match name.rsplit_once('.') {
+                Some((stem, extension)) if !stem.is_empty() => {
+                    format!("{}-{:x}.{}", stem, get_digest(query.as_bytes()), extension)
+                }
+                _ => format!("{}-{:x}", name, get_digest(query.as_bytes())),
+            },
+            _ => name,
+        };
+
+        for segment in segments {
+            path.push(segment);
+        }
+        path.push(name);
+
+        path
+    }
+
+    /// Write the asset to its file and record it to the manifest creating the directories when
+    /// missing.
+    pub fn save(
+        &self,
+        url: &Url,
+        page_url: &str,
+        content_type: &str,
+        content: &[u8],
+    ) -> std::io::Result<AssetEntry> {
+        let path = self.get_path(url);
+
+        match path.parent() {
+            Some(dir) if !dir.as_os_str().is_empty() => std::fs::create_dir_all(dir)?,
+            _ => (),
+        }
+        std::fs::write(&path, content)?;
+
+        let entry = AssetEntry {
+            url: url.as_str().into(),
+            path,
+            content_type: content_type.into(),
+            size: content.len(),
+            page_url: page_url.into(),
+        };
+
+        match self.entries.lock() {
+            Ok(mut entries) => {
+                let manifest = self.download.dir.join(MANIFEST_FILE);
+                let mut file = std::fs::OpenOptions::new()
+                    .create(true)
+                    .append(true)
+                    .open(&manifest)?;
+
+                if file.metadata()?.len() == 0 {
+                    file.write_all(b"url,path,content_type,size,page_url\n")?;
+                }
+                file.write_all(
+                    format!(
+                        "{},{},{},{},{}\n",
+                        escape_csv(&entry.url),
+                        escape_csv(&entry.path.to_string_lossy()),
+                        escape_csv(&entry.content_type),
+                        entry.size,
+                        escape_csv(&entry.page_url)
+                    )
+                    .as_bytes(),
+                )?;
+                entries.push(entry.clone());
+            }
+            _ => (),
+        }
+
+        Ok(entry)
+    }
+
+    /// Get the assets written.
+    pub fn get_entries(&self) -> Vec<AssetEntry> {
+        match self.entries.lock() {
+            Ok(entries) => entries.clone(),
+            _ => Default::default(),
+        }
+    }
+
+    /// Remove all the assets found and written. The files are kept.
+    pub fn clear(&self) {
+        match self.urls.lock() {
+            Ok(mut urls) => urls.clear(),
+            _ => (),
+        }
+
+        match self.entries.lock() {
+            Ok(mut entries) => entries.clear(),
+            _ => (),
+        }
+    }
+}
+
+/// Get the extension of the last segment of the path of the url.
+pub fn get_extension(url: &Url) -> Option<&str> {
+    url.path_segments()
+        .and_then(|mut segments| segments.next_back())
+        .and_then(|name| name.rsplit_once('.'))
+        .map(|(_, extension)| extension)
+        .filter(|extension| !extension.is_empty())
+}
+
+/// Download the asset and write it with the store. Returns `None` when the request fails, the
+/// content type is not allowed, or the asset is larger than the max size.
+pub async fn download_asset(
+    client: &Client,
+    store: &AssetStore,
+    url: &Url,
+    page_url: &str,
+) -> Option<AssetEntry> {
+    use tokio_stream::StreamExt;
+
+    let download = store.get_download();
+    let response = send_request(url.as_str(), client, Default::default())
+        .await
+        .ok()?;
+
+    if !response.status().is_success() {
+        return None;
+    }
+
+    let content_type = response
+        .headers()
+        .get(CONTENT_TYPE)
+        .and_then(|content_type| content_type.to_str().ok())
+        .unwrap_or_default()
+        .to_string();
+
+    match &download.content_types {
+        Some(content_types) if !content_types.is_allowed(&content_type) => return None,
+        _ => (),
+    }
+
+    match response.content_length() {
+        Some(size) if download.max_size > 0 && size as usize > download.max_size => return None,
+        _ => (),
+    }
+
+    let content_encoding = ContentEncoding::from_headers(response.headers());
+    let mut stream = get_body_stream(response, content_encoding);
+    let mut content: Vec<u8> = Vec::new();
+
+    while let Some(item) = stream.next().await {
+        match item {
+            Ok(bytes)
+                if download.max_size > 0 && content.len() + bytes.len() > download.max_size =>
+            {
+                return None
+            }
+            Ok(bytes) => content.extend_from_slice(&bytes),
+            _ => return None,
+        }
+    }
+
+    match store.save(url, page_url, &content_type, &content) {
+        Ok(entry) => Some(entry),
+        Err(e) => {
+            log("asset write failed - ", e.to_string());
+            None
+        }
+    }
+}
@@ -0,0 +1,69 @@
+use std::sync::Mutex;
+use std::time::Duration;
+use tokio::time::Instant;
+
+/// The bytes of the bucket and the time they were refilled at.
+#[derive(Debug)]
+struct BandwidthState {
+    /// The bytes left, negative with the chunks granted ahead of the refill.
+    bytes: f64,
+    /// The time the bytes were refilled at.
+    refilled: Instant,
+}
+
+/// The token bucket of the bytes per second downloaded shared by the requests of a crawl.
+#[derive(Debug)]
+pub struct BandwidthLimiter {
+    /// The bytes per second refilling the bucket up to a second of download.
+    bytes_per_second: u64,
+    /// The bytes of the bucket.
+    state: Mutex<BandwidthState>,
+}
+
+impl BandwidthLimiter {
+    /// Establish the limiter of the bytes per second downloaded.
+    pub fn new(bytes_per_second: u64) -> Self {
+        Self {
+            bytes_per_second: bytes_per_second.max(1),
+            state: Mutex::new(BandwidthState {
+                bytes: bytes_per_second.max(1) as f64,
+                refilled: Instant::now(),
+            }),
+        }
+    }
+
+    /// Get the bytes per second downloaded.
+    pub fn get_bytes_per_second(&self) -> u64 {
+        self.bytes_per_second
+    }
+
+    /// Take the bytes of the chunk downloaded waiting until the bucket refills them.
+    pub async fn acquire(&self, bytes: usize) {
+        let wait = match self.state.lock() {
+            Ok(mut state) => {
+                let now = Instant::now();
+                let rate = self.bytes_per_second as f64;
+
+                if now > state.refilled {
+                    let refill = (now - state.refilled).as_secs_f64() * rate;
+
+                    state.bytes = (state.bytes + refill).min(rate);
+                    state.refilled = now;
+                }
+
+                state.bytes -= bytes as f64;
+
+                if state.bytes < 0.0 {
+                    Duration::from_secs_f64(-state.bytes / rate)
+                } else {
+                    Duration::ZERO
+                }
+            }
+            _ => Duration::ZERO,
+        };
+
+        if !wait.is_zero() {
+            tokio::time::sleep(wait).await;
+        }
+    }
+}
@@ -0,0 +1,143 @@
+use reqwest::header::{HeaderMap, SERVER};
+use reqwest::StatusCode;
+use std::time::Duration;
+
+/// The max bytes of the html searched for the markers of the challenges.
+const CHALLENGE_SCAN_SIZE: usize = 64 * 1024;
+
+/// The bot protection challenging a page.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Challenge {
+    /// The Cloudflare browser check or managed challenge.
+    Cloudflare,
+    /// The Akamai Bot Manager interstitial or access denied page.
+    Akamai,
+    /// The DataDome captcha.
+    DataDome,
+    /// The PerimeterX (HUMAN) press and hold captcha.
+    PerimeterX,
+    /// Another CAPTCHA wall like reCAPTCHA, hCaptcha, or Turnstile.
+    Captcha,
+}
+
+/// The action of the crawl on a page challenged.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ChallengeAction {
+    /// Skip the page marking it as failed.
+    Skip,
+    /// Request the page again once with the headers of the challenge solved like the cookie or the
+    /// token. The page is skipped when challenged again.
+    Retry(HeaderMap),
+    /// Pause the host for the duration and request the page again once.
+    Backoff(Duration),
+}
+
+impl Challenge {
+    /// Detect the challenge of the response from the status code, the headers, and the html.
+    pub fn detect(
+        status_code: StatusCode,
+        headers: Option<&HeaderMap>,
+        html: &[u8],
+    ) -> Option<Self> {
+        let header = |name: &str| {
+            headers
+                .and_then(|headers| headers.get(name))
+                .and_then(|value| value.to_str().ok())
+                .map(|value| value.to_ascii_lowercase())
+                .unwrap_or_default()
+        };
+        let html = String::from_utf8_lossy(&html[..html.len().min(CHALLENGE_SCAN_SIZE)])
+            .to_ascii_lowercase();
+        let contains = |markers: &[&str]| markers.iter().any(|marker| html.contains(marker));
+        let blocked = status_code == StatusCode::FORBIDDEN
+            || status_code == StatusCode::TOO_MANY_REQUESTS
+            || status_code == StatusCode::SERVICE_UNAVAILABLE;
+        let server = header(SERVER.as_str());
+
+        // the scripts of the challenge platform are also injected in the pages not challenged
+        if header("cf-mitigated") == "challenge"
+            || (blocked
+                && server.contains("cloudflare")
+                && contains(&[
+                    "cf_chl_opt",
+                    "/cdn-cgi/challenge-platform/",
+                    "<title>just a moment...</title>",
+                    "<title>attention required! | cloudflare</title>",
+                ]))
+        {
+            Some(Challenge::Cloudflare)
+        } else if contains(&["bm-verify", "sec-if-cpt-container", "_sec/cp_challenge"])
+            || (blocked
+                && server.contains("akamaighost")
+                && contains(&["<title>access denied</title>"]))
+        {
+            Some(Challenge::Akamai)
+        } else if (blocked && !header("x-datadome").is_empty())
+            || contains(&["captcha-delivery.com", "geo.captcha-delivery"])
+        {
+            Some(Challenge::DataDome)
+        } else if contains(&["px-captcha", "_pxcaptcha", "captcha.px-cdn.net"]) {
+            Some(Challenge::PerimeterX)
+        } else if contains(&[
+            "g-recaptcha",
+            "h-captcha",
+            "hcaptcha.com/1/api.js",
+            "cf-turnstile",
+        ]) && (blocked
+            || contains(&[
+                "are you a robot",
+                "are you human",
+                "verify you are human",
+                "unusual traffic",
+                "security check",
+            ]))
+        {
+            Some(Challenge::Captcha)
+        } else {
+            None
+        }
+    }
+}
+
+#[test]
+fn test_challenge_detect() {
+    use reqwest::header::HeaderValue;
+
+    let mut headers = HeaderMap::new();
+    headers.insert(SERVER, HeaderValue::from_static("cloudflare"));
+
+    let html = br#"<html><head><title>Just a moment...</title></head><body>
+        <script src="/cdn-cgi/challenge-platform/h/g/orchestrate/chl_page/v1"></script></body></html>"#;
+
+    assert_eq!(
+        Challenge::detect(StatusCode::FORBIDDEN, Some(&headers), html),
+        Some(Challenge::Cloudflare)
+    );
+    assert_eq!(
+        Challenge::detect(StatusCode::OK, Some(&headers), html),
+        None
+    );
+
+    headers.insert("cf-mitigated", HeaderValue::from_static("challenge"));
+
+    assert_eq!(
+        Challenge::detect(StatusCode::OK, Some(&headers), b""),
+        Some(Challenge::Cloudflare)
+    );
+
+    let html = br#"<html><body><h1>Are you a robot?</h1>
+        <div class="g-recaptcha" data-sitekey="key"></div></body></html>"#;
+
+    assert_eq!(
+        Challenge::detect(StatusCode::OK, None, html),
+        Some(Challenge::Captcha)
+    );
+    assert_eq!(
+        Challenge::detect(
+            StatusCode::OK,
+            None,
+            b"<html><body>MIT License</body></html>"
+        ),
+        None
+    );
+}
@@ -1,14 +1,56 @@
+use crate::configuration::{
+    Configuration, InterceptBlocklist, PdfParams, ScreenshotFormat, ScreenshotParams,
+    StealthProfile, WaitFor,
+};
 use crate::tokio_stream::StreamExt;
-use crate::utils::log;
-use chromiumoxide::{Browser, BrowserConfig};
+use crate::utils::{escape_json, log};
+use chromiumoxide::cdp::browser_protocol::emulation::{
+    SetDeviceMetricsOverrideParams, SetLocaleOverrideParams, SetTimezoneOverrideParams,
+};
+use chromiumoxide::cdp::browser_protocol::fetch::{
+    ContinueRequestParams, EnableParams, EventRequestPaused, FailRequestParams,
+};
+use chromiumoxide::cdp::browser_protocol::network::{ErrorReason, SetUserAgentOverrideParams};
+use chromiumoxide::cdp::browser_protocol::page::{
+    AddScriptToEvaluateOnNewDocumentParams, CaptureScreenshotFormat, EventDomContentEventFired,
+    EventLifecycleEvent, NavigateParams, PrintToPdfParams,
+};
+use chromiumoxide::handler::http::HttpRequest;
+use chromiumoxide::{Browser, BrowserConfig, Page};
+use std::path::Path;
+use std::sync::Arc;
+use std::time::Duration;
 use tokio::task;
 
+/// Get the proxies of the `--proxy-server` argument without their user info. The `socks5h://`
+/// proxies are passed as `socks5://` resolving the hosts on the proxy by default in chrome.
+pub fn get_proxy_server(proxies: &[String]) -> String {
+    proxies
+        .iter()
+        .map(|proxy| match url::Url::parse(proxy) {
+            Ok(url) if url.host_str().is_some() => {
+                let scheme = match url.scheme() {
+                    "socks5h" => "socks5",
+                    scheme => scheme,
+                };
+                let host = url.host_str().unwrap_or_default();
+
+                match url.port_or_known_default() {
+                    Some(port) => format!("{}://{}:{}", scheme, host, port),
+                    _ => format!("{}://{}", scheme, host),
+                }
+            }
+            _ => proxy.into(),
+        })
+        .collect::<Vec<String>>()
+        .join(";")
+}
+
 /// get chrome configuration
 #[cfg(not(feature = "chrome_headed"))]
 pub fn get_browser_config(
     proxies: &Option<Box<Vec<string_concat::String>>>,
 ) -> Option<BrowserConfig> {
-    use std::time::Duration;
     let builder = BrowserConfig::builder()
         .disable_default_args()
         .request_timeout(Duration::from_secs(30));
@@ -19,7 +61,7 @@ pub fn get_browser_config(
 
             chrome_args.push(string_concat!(
                 r#"--proxy-server=""#,
-                proxies.join(";"),
+                get_proxy_server(proxies),
                 r#"""#
             ));
 
@@ -49,7 +91,6 @@ pub fn get_browser_config(
 pub fn get_browser_config(
     proxies: &Option<Box<Vec<string_concat::String>>>,
 ) -> Option<BrowserConfig> {
-    use std::time::Duration;
     let builder = BrowserConfig::builder()
         .disable_default_args()
         .request_timeout(Duration::from_secs(30))
@@ -68,7 +109,7 @@ pub fn get_browser_config(
         Some(proxies) => {
             chrome_args.push(string_concat!(
                 r#"--proxy-server=""#,
-                proxies.join(";"),
+                get_proxy_server(proxies),
                 r#"""#
             ));
 
@@ -129,6 +170,365 @@ pub async fn launch_browser(
     }
 }
 
+/// Setup the new page of the crawl with the stealth evasions and the requests intercepted of the
+/// configuration.
+pub async fn configure_page(page: &Page, configuration: &Configuration) {
+    match &configuration.stealth {
+        Some(profile) => stealth(page, &configuration.user_agent, profile).await,
+        _ => (),
+    }
+    match &configuration.intercept_blocklist {
+        Some(blocklist) => intercept_requests(page, blocklist).await,
+        _ => (),
+    }
+}
+
+/// Apply the stealth evasions of the profile to the page before its documents are loaded.
+pub async fn stealth(
+    page: &Page,
+    user_agent: &Option<Box<compact_str::CompactString>>,
+    profile: &StealthProfile,
+) {
+    let user_agent = match user_agent {
+        Some(user_agent) => user_agent.to_string(),
+        _ => match page.evaluate("navigator.userAgent").await {
+            Ok(result) => result.into_value::<String>().unwrap_or_default(),
+            _ => Default::default(),
+        },
+    }
+    .replace("HeadlessChrome", "Chrome");
+
+    match SetUserAgentOverrideParams::builder()
+        .user_agent(user_agent)
+        .accept_language(profile.get_accept_language())
+        .platform(profile.platform.as_str())
+        .build()
+    {
+        Ok(params) => {
+            let _ = page.execute(params).await;
+        }
+        _ => (),
+    }
+
+    let _ = page
+        .execute(SetDeviceMetricsOverrideParams::new(
+            profile.viewport.0.into(),
+            profile.viewport.1.into(),
+            1.0,
+            false,
+        ))
+        .await;
+    let _ = page
+        .execute(
+            SetLocaleOverrideParams::builder()
+                .locale(profile.locale.as_str())
+                .build(),
+        )
+        .await;
+
+    match &profile.timezone {
+        Some(timezone) => {
+            let _ = page
+                .execute(SetTimezoneOverrideParams::new(timezone.as_str()))
+                .await;
+        }
+        _ => (),
+    }
+
+    match page
+        .execute(AddScriptToEvaluateOnNewDocumentParams::new(
+            get_stealth_script(profile),
+        ))
+        .await
+    {
+        Ok(_) => (),
+        Err(e) => log::error!("failed to add the stealth script: {:?}", e),
+    }
+}
+
+/// Get the script of the evasions of the profile to run before the scripts of the documents.
+pub fn get_stealth_script(profile: &StealthProfile) -> String {
+    let language = profile
+        .locale
+        .split_once('-')
+        .map_or(profile.locale.as_str(), |(language, _)| language);
+    let languages = if language == profile.locale.as_str() {
+        format!(r#"["{}"]"#, escape_json(language))
+    } else {
+        format!(
+            r#"["{}","{}"]"#,
+            escape_json(&profile.locale),
+            escape_json(language)
+        )
+    };
+
+    format!(
+        concat!(
+            "(() => {{",
+            "const define = (target, name, value) => Object.defineProperty(target, name, {{ get: () => value, configurable: true }});",
+            "define(Navigator.prototype, 'webdriver', undefined);",
+            "define(Navigator.prototype, 'languages', Object.freeze({}));",
+            r#"define(Navigator.prototype, 'platform', "{}");"#,
+            "define(Navigator.prototype, 'hardwareConcurrency', {});",
+            "window.chrome = window.chrome || {{ runtime: {{}} }};",
+            "for (const context of [window.WebGLRenderingContext, window.WebGL2RenderingContext]) {{",
+            "if (!context) continue;",
+            "const getParameter = context.prototype.getParameter;",
+            "context.prototype.getParameter = function (parameter) {{",
+            r#"if (parameter === 37445) return "{}";"#,
+            r#"if (parameter === 37446) return "{}";"#,
+            "return getParameter.call(this, parameter);",
+            "}};",
+            "}}",
+            "}})();"
+        ),
+        languages,
+        escape_json(&profile.platform),
+        profile.hardware_concurrency,
+        escape_json(&profile.webgl_vendor),
+        escape_json(&profile.webgl_renderer)
+    )
+}
+
+/// Intercept the requests of the page to fail the requests blocked. The requests are paused until
+/// continued by the task spawned for the lifetime of the page.
+pub async fn intercept_requests(page: &Page, blocklist: &InterceptBlocklist) {
+    let mut events = match page.event_listener::<EventRequestPaused>().await {
+        Ok(events) => events,
+        Err(e) => {
+            log::error!("failed to listen to the requests: {:?}", e);
+            return;
+        }
+    };
+
+    match page.execute(EnableParams::default()).await {
+        Ok(_) => {
+            let page = page.clone();
+            let blocklist = blocklist.clone();
+
+            task::spawn(async move {
+                while let Some(event) = events.next().await {
+                    let request_id = event.request_id.clone();
+
+                    if blocklist.is_blocked(&event.request.url, event.resource_type.as_ref()) {
+                        let _ = page
+                            .execute(FailRequestParams::new(
+                                request_id,
+                                ErrorReason::BlockedByClient,
+                            ))
+                            .await;
+                    } else {
+                        let _ = page.execute(ContinueRequestParams::new(request_id)).await;
+                    }
+                }
+            });
+        }
+        Err(e) => log::error!("failed to intercept the requests: {:?}", e),
+    }
+}
+
+/// Navigate the page to the url and wait for the condition before extracting its html. Returns the
+/// request of the navigation when the page loaded to follow its redirects.
+pub async fn navigate(
+    page: &Page,
+    target_url: &str,
+    wait_for: &WaitFor,
+) -> chromiumoxide::error::Result<Option<Arc<HttpRequest>>> {
+    match wait_for {
+        WaitFor::DomContentLoaded => {
+            // listen before navigating to not miss the event of the fast pages
+            let mut events = page.event_listener::<EventDomContentEventFired>().await?;
+            page.execute(NavigateParams::new(target_url)).await?;
+            let _ = tokio::time::timeout(Duration::from_secs(30), events.next()).await;
+
+            Ok(None)
+        }
+        WaitFor::NetworkIdle(timeout) => {
+            let mut events = page.event_listener::<EventLifecycleEvent>().await?;
+            let request = page
+                .goto(target_url)
+                .await?
+                .wait_for_navigation_response()
+                .await
+                .unwrap_or_default();
+
+            let _ = tokio::time::timeout(*timeout, async {
+                while let Some(event) = events.next().await {
+                    if event.name == "networkIdle" {
+                        break;
+                    }
+                }
+            })
+            .await;
+
+            Ok(request)
+        }
+        _ => {
+            let request = page
+                .goto(target_url)
+                .await?
+                .wait_for_navigation_response()
+                .await
+                .unwrap_or_default();
+
+            match wait_for {
+                WaitFor::Selector(selector, timeout) => {
+                    let _ = tokio::time::timeout(*timeout, async {
+                        while page.find_element(selector.as_str()).await.is_err() {
+                            tokio::time::sleep(Duration::from_millis(100)).await;
+                        }
+                    })
+                    .await;
+                }
+                WaitFor::Delay(delay) => tokio::time::sleep(*delay).await,
+                _ => (),
+            }
+
+            Ok(request)
+        }
+    }
+}
+
+/// Run the login script on the form page keeping the session cookies in the browser. False when the
+/// login failed.
+pub async fn login(page: &Page, form_page: &str, script: &str) -> bool {
+    match page.goto(form_page).await {
+        Ok(page) => match page.evaluate(script).await {
+            Ok(_) => {
+                // the form submitted navigates to the page of the session
+                let _ =
+                    tokio::time::timeout(Duration::from_secs(30), page.wait_for_navigation()).await;
+                log("login succeeded", form_page);
+                true
+            }
+            Err(e) => {
+                log("login failed", e.to_string());
+                false
+            }
+        },
+        Err(e) => {
+            log("login form page failed", e.to_string());
+            false
+        }
+    }
+}
+
+/// Capture the screenshot of the page rendered writing it to the output directory when set.
+/// Returns the screenshot when attached to the page.
+pub async fn screenshot(
+    page: &Page,
+    target_url: &str,
+    params: &ScreenshotParams,
+) -> Option<bytes::Bytes> {
+    let mut builder = chromiumoxide::page::ScreenshotParams::builder()
+        .format(match params.format {
+            ScreenshotFormat::Png => CaptureScreenshotFormat::Png,
+            ScreenshotFormat::Jpeg => CaptureScreenshotFormat::Jpeg,
+        })
+        .full_page(params.full_page)
+        .omit_background(params.omit_background);
+
+    match params.quality {
+        Some(quality) => builder = builder.quality(quality),
+        _ => (),
+    }
+
+    let screenshot = match page.screenshot(builder.build()).await {
+        Ok(screenshot) => screenshot,
+        Err(e) => {
+            log::error!("failed to capture screenshot: {:?} - {}", e, target_url);
+            return None;
+        }
+    };
+
+    match &params.output_dir {
+        Some(output_dir) => {
+            save_capture(
+                output_dir,
+                target_url,
+                params.format.extension(),
+                &screenshot,
+            )
+            .await
+        }
+        _ => (),
+    }
+
+    if params.bytes {
+        Some(screenshot.into())
+    } else {
+        None
+    }
+}
+
+/// Print the page rendered to a PDF writing it to the output directory when set. Returns the PDF
+/// when attached to the page.
+pub async fn pdf(page: &Page, target_url: &str, params: &PdfParams) -> Option<bytes::Bytes> {
+    let mut builder = PrintToPdfParams::builder()
+        .landscape(params.landscape)
+        .print_background(params.print_background)
+        .prefer_css_page_size(params.prefer_css_page_size);
+
+    match params.scale {
+        Some(scale) => builder = builder.scale(scale),
+        _ => (),
+    }
+    match params.paper_size {
+        Some((paper_width, paper_height)) => {
+            builder = builder.paper_width(paper_width).paper_height(paper_height)
+        }
+        _ => (),
+    }
+
+    let pdf = match page.pdf(builder.build()).await {
+        Ok(pdf) => pdf,
+        Err(e) => {
+            log::error!("failed to print pdf: {:?} - {}", e, target_url);
+            return None;
+        }
+    };
+
+    match &params.output_dir {
+        Some(output_dir) => save_capture(output_dir, target_url, "pdf", &pdf).await,
+        _ => (),
+    }
+
+    if params.bytes {
+        Some(pdf.into())
+    } else {
+        None
+    }
+}
+
+/// Write the capture of the page to the output directory creating it when missing.
+async fn save_capture(output_dir: &Path, target_url: &str, extension: &str, capture: &[u8]) {
+    let output_path = output_dir.join(get_capture_name(target_url, extension));
+
+    match tokio::fs::create_dir_all(output_dir).await {
+        Ok(_) => match tokio::fs::write(&output_path, capture).await {
+            Ok(_) => log::debug!("saved capture: {:?}", output_path),
+            Err(e) => log::error!("failed to save capture: {:?} - {:?}", e, output_path),
+        },
+        Err(e) => log::error!("failed to create directory: {:?} - {:?}", e, output_dir),
+    }
+}
+
+/// Get the file name of the capture of the url replacing the characters unsafe for a path.
+pub fn get_capture_name(target_url: &str, extension: &str) -> String {
+    let name: String = target_url
+        .split_once("://")
+        .map_or(target_url, |(_, rest)| rest)
+        .trim_end_matches('/')
+        .chars()
+        .map(|c| match c {
+            'a'..='z' | 'A'..='Z' | '0'..='9' | '-' | '.' => c,
+            _ => '_',
+        })
+        .collect();
+
+    string_concat!(name, ".", extension)
+}
+
 #[cfg(not(feature = "chrome_cpu"))]
 /// static chrome arguments to start application ref [https://github.com/a11ywatch/chrome/blob/main/src/main.rs#L13]
 static CHROME_ARGS: [&'static str; 59] = [
@@ -0,0 +1,525 @@
+use crate::CaseInsensitiveString;
+use hashbrown::{HashMap, HashSet};
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// The request of a worker for a lease of links.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LeaseRequest {
+    /// The name of the worker.
+    pub worker: String,
+    /// The max links leased. Use 0 to lease the max links of a lease of the coordinator.
+    pub max: usize,
+}
+
+/// A link leased with its depth.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LeasedLink {
+    /// The link to fetch.
+    pub url: String,
+    /// The link hops from the start url.
+    pub depth: usize,
+}
+
+/// The links leased to a worker to fetch and report before the lease expires.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Lease {
+    /// The id of the lease to report.
+    pub id: u64,
+    /// The links to fetch. Empty while the links are leased to other workers.
+    pub links: Vec<LeasedLink>,
+    /// The crawl is done and the worker stops.
+    pub done: bool,
+}
+
+/// The page of a link leased fetched by a worker.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PageReport {
+    /// The link leased.
+    pub url: String,
+    /// The status code of the response.
+    pub status_code: u16,
+    /// The request failed.
+    pub failed: bool,
+    /// The links found on the page.
+    pub links: Vec<String>,
+}
+
+/// The pages of a lease reported by a worker.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Report {
+    /// The id of the lease.
+    pub lease: u64,
+    /// The pages of the links leased fetched.
+    pub pages: Vec<PageReport>,
+}
+
+/// The progress of the crawl of the coordinator.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CoordinatorStatus {
+    /// The count of the links queued.
+    pub queued: usize,
+    /// The count of the links leased not reported.
+    pub leased: usize,
+    /// The count of the pages reported.
+    pub crawled: usize,
+    /// The count of the pages reported failed.
+    pub failed: usize,
+    /// The crawl is done.
+    pub done: bool,
+}
+
+/// The budgets of the crawl of the coordinator.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CrawlBudget {
+    /// The max pages crawled. Defaults to 0 to crawl all the pages.
+    pub max_pages: usize,
+    /// The max link hops from the start url. Defaults to 0 to crawl the links of any depth.
+    pub max_depth: usize,
+    /// The max pages crawled per host. Defaults to 0 to crawl all the pages of a host.
+    pub max_pages_per_host: usize,
+    /// The max links of a lease. Defaults to 100.
+    pub max_lease: usize,
+    /// The duration to report a lease before its links are queued again. Defaults to 60 seconds.
+    pub lease_timeout: Duration,
+}
+
+impl Default for CrawlBudget {
+    fn default() -> Self {
+        Self {
+            max_pages: 0,
+            max_depth: 0,
+            max_pages_per_host: 0,
+            max_lease: 100,
+            lease_timeout: Duration::from_secs(60),
+        }
+    }
+}
+
+/// The links of a lease held by a worker.
+#[derive(Debug)]
+struct HeldLease {
+    /// The name of the worker.
+    worker: String,
+    /// The time the lease expires.
+    expires: Instant,
+    /// The links leased.
+    links: Vec<LeasedLink>,
+}
+
+/// The frontier, the leases, and the results of the crawl.
+#[derive(Debug, Default)]
+struct CoordinatorState {
+    /// The links queued to lease in breadth-first order.
+    queue: VecDeque<LeasedLink>,
+    /// The links queued once to skip the links found again.
+    seen: HashSet<CaseInsensitiveString>,
+    /// The leases held by the workers.
+    leases: HashMap<u64, HeldLease>,
+    /// The id of the last lease.
+    lease_id: u64,
+    /// The pages leased or crawled per host.
+    hosts: HashMap<String, usize>,
+    /// The status code of the pages reported.
+    results: HashMap<String, u16>,
+    /// The count of the pages reported failed.
+    failed: usize,
+}
+
+/// The coordinator of a distributed crawl leasing the links to the workers.
+#[derive(Debug)]
+pub struct Coordinator {
+    /// The budgets of the crawl.
+    budget: CrawlBudget,
+    /// The frontier, the leases, and the results of the crawl.
+    state: Mutex<CoordinatorState>,
+}
+
+impl Coordinator {
+    /// Establish the coordinator of the crawl of the url within the budgets.
+    pub fn new(url: &str, budget: CrawlBudget) -> Self {
+        let mut state = CoordinatorState::default();
+
+        state.seen.insert(url.into());
+        state.queue.push_back(LeasedLink {
+            url: url.into(),
+            depth: 0,
+        });
+
+        Self {
+            budget,
+            state: Mutex::new(state),
+        }
+    }
+
+    /// Get the budgets of the crawl.
+    pub fn get_budget(&self) -> &CrawlBudget {
+        &self.budget
+    }
+
+    /// Lease the next links queued to the worker within the budgets. The links of the leases
+    /// expired are queued again first.
+    pub fn lease(&self, request: &LeaseRequest) -> Lease {
+        let mut state = match self.state.lock() {
+            Ok(state) => state,
+            _ => return Default::default(),
+        };
+
+        self.expire(&mut state, Instant::now());
+
+        let leased: usize = state.leases.values().map(|lease| lease.links.len()).sum();
+        let mut max = match request.max {
+            0 => self.budget.max_lease,
+            max => max.min(self.budget.max_lease.max(1)),
+        };
+
+        if self.budget.max_pages > 0 {
+            max = max.min(
+                self.budget
+                    .max_pages
+                    .saturating_sub(state.results.len() + leased),
+            );
+        }
+
+        let mut links = Vec::new();
+
+        while links.len() < max {
+            let link = match state.queue.pop_front() {
+                Some(link) => link,
+                _ => break,
+            };
+            let host = get_host(&link.url);
+            let count = state.hosts.entry(host).or_default();
+
+            // the links of the hosts over their budget are dropped
+            if self.budget.max_pages_per_host > 0 && *count >= self.budget.max_pages_per_host {
+                continue;
+            }
+
+            *count += 1;
+            links.push(link);
+        }
+
+        if links.is_empty() {
+            return Lease {
+                id: 0,
+                links,
+                done: self.is_done_state(&state),
+            };
+        }
+
+        state.lease_id += 1;
+
+        let id = state.lease_id;
+
+        state.leases.insert(
+            id,
+            HeldLease {
+                worker: request.worker.clone(),
+                expires: Instant::now() + self.budget.lease_timeout,
+                links: links.clone(),
+            },
+        );
+
+        Lease {
+            id,
+            links,
+            done: false,
+        }
+    }
+
+    /// Record the pages of the lease reported queueing the links found within the max depth. The
+    /// links of the lease not reported are queued again. Returns false when the lease is not held
+    /// as expired.
+    pub fn report(&self, report: Report) -> bool {
+        let mut state = match self.state.lock() {
+            Ok(state) => state,
+            _ => return false,
+        };
+        let lease = match state.leases.remove(&report.lease) {
+            Some(lease) => lease,
+            _ => return false,
+        };
+        let mut pages: HashMap<String, PageReport> = report
+            .pages
+            .into_iter()
+            .map(|page| (page.url.clone(), page))
+            .collect();
+
+        let mut unreported = Vec::new();
+
+        for link in lease.links {
+            let page = match pages.remove(&link.url) {
+                Some(page) => page,
+                _ => {
+                    unreported.push(link);
+                    continue;
+                }
+            };
+
+            state.results.insert(link.url, page.status_code);
+
+            if page.failed {
+                state.failed += 1;
+            }
+
+            let depth = link.depth + 1;
+
+            if self.budget.max_depth > 0 && depth > self.budget.max_depth {
+                continue;
+            }
+
+            for url in page.links {
+                if state.seen.insert(url.as_str().into()) {
+                    state.queue.push_back(LeasedLink { url, depth });
+                }
+            }
+        }
+
+        for link in unreported.into_iter().rev() {
+            release_host(&mut state, &link.url);
+            state.queue.push_front(link);
+        }
+
+        true
+    }
+
+    /// Get the progress of the crawl.
+    pub fn get_status(&self) -> CoordinatorStatus {
+        match self.state.lock() {
+            Ok(mut state) => {
+                self.expire(&mut state, Instant::now());
+
+                CoordinatorStatus {
+                    queued: state.queue.len(),
+                    leased: state.leases.values().map(|lease| lease.links.len()).sum(),
+                    crawled: state.results.len(),
+                    failed: state.failed,
+                    done: self.is_done_state(&state),
+                }
+            }
+            _ => Default::default(),
+        }
+    }
+
+    /// Get the status code of the pages reported.
+    pub fn get_results(&self) -> HashMap<String, u16> {
+        match self.state.lock() {
+            Ok(state) => state.results.clone(),
+            _ => Default::default(),
+        }
+    }
+
+    /// Get the workers holding a lease.
+    pub fn get_workers(&self) -> HashSet<String> {
+        match self.state.lock() {
+            Ok(state) => state
+                .leases
+                .values()
+                .map(|lease| lease.worker.clone())
+                .collect(),
+            _ => Default::default(),
+        }
+    }
+
+    /// The crawl is done once the leases are reported when no links are queued or the pages budget
+    /// is spent.
+    pub fn is_done(&self) -> bool {
+        self.get_status().done
+    }
+
+    /// The crawl of the state is done.
+    fn is_done_state(&self, state: &CoordinatorState) -> bool {
+        state.leases.is_empty()
+            && (state.queue.is_empty()
+                || (self.budget.max_pages > 0 && state.results.len() >= self.budget.max_pages))
+    }
+
+    /// Queue again the links of the leases expired.
+    fn expire(&self, state: &mut CoordinatorState, now: Instant) {
+        let expired: Vec<u64> = state
+            .leases
+            .iter()
+            .filter(|(_, lease)| lease.expires <= now)
+            .map(|(id, _)| *id)
+            .collect();
+
+        for id in expired {
+            match state.leases.remove(&id) {
+                Some(lease) => {
+                    for link in lease.links.into_iter().rev() {
+                        release_host(state, &link.url);
+                        state.queue.push_front(link);
+                    }
+                }
+                _ => (),
+            }
+        }
+    }
+}
+
+/// Get the host of the url lowercase.
+fn get_host(url: &str) -> String {
+    match url::Url::parse(url) {
+        Ok(url) => url.host_str().unwrap_or_default().to_ascii_lowercase(),
+        _ => Default::default(),
+    }
+}
+
+/// Release the page leased of the host of the url from its budget.
+fn release_host(state: &mut CoordinatorState, url: &str) {
+    match state.hosts.get_mut(&get_host(url)) {
+        Some(count) => *count = count.saturating_sub(1),
+        _ => (),
+    }
+}
+
+/// Post the message as JSON to the path of the coordinator replying JSON.
+async fn post_json<T: Serialize, R: serde::de::DeserializeOwned>(
+    client: &reqwest::Client,
+    url: &str,
+    message: &T,
+) -> Option<R> {
+    let response = client
+        .post(url)
+        .header(reqwest::header::CONTENT_TYPE, "application/json")
+        .body(serde_json::to_vec(message).ok()?)
+        .send()
+        .await
+        .ok()?;
+
+    serde_json::from_slice(&response.bytes().await.ok()?).ok()
+}
+
+/// Request a lease of links from the coordinator of the url like `http://127.0.0.1:3032`.
+pub async fn request_lease(
+    client: &reqwest::Client,
+    coordinator: &str,
+    request: &LeaseRequest,
+) -> Option<Lease> {
+    post_json(
+        client,
+        &format!("{}/lease", coordinator.trim_end_matches('/')),
+        request,
+    )
+    .await
+}
+
+/// Send the report of a lease to the coordinator of the url. Returns false when the lease is not
+/// held.
+pub async fn send_report(
+    client: &reqwest::Client,
+    coordinator: &str,
+    report: &Report,
+) -> Option<bool> {
+    post_json(
+        client,
+        &format!("{}/report", coordinator.trim_end_matches('/')),
+        report,
+    )
+    .await
+}
+
+#[test]
+fn test_coordinator() {
+    let coordinator = Coordinator::new(
+        "https://choosealicense.com/",
+        CrawlBudget {
+            max_pages: 3,
+            max_depth: 1,
+            ..Default::default()
+        },
+    );
+    let request = LeaseRequest {
+        worker: "worker-1".into(),
+        max: 10,
+    };
+    let page = |url: &str, links: &[&str]| PageReport {
+        url: url.into(),
+        status_code: 200,
+        failed: false,
+        links: links.iter().map(|link| link.to_string()).collect(),
+    };
+
+    let lease = coordinator.lease(&request);
+
+    assert_eq!(lease.links.len(), 1);
+    assert!(!lease.done);
+    assert!(coordinator.get_workers().contains("worker-1"));
+    assert!(coordinator.lease(&request).links.is_empty());
+    assert!(!coordinator.is_done());
+
+    assert!(coordinator.report(Report {
+        lease: lease.id,
+        pages: vec![page(
+            "https://choosealicense.com/",
+            &[
+                "https://choosealicense.com/licenses/",
+                "https://choosealicense.com/about/",
+                "https://choosealicense.com/community/",
+                "https://choosealicense.com/",
+            ],
+        )],
+    }));
+    assert!(!coordinator.report(Report {
+        lease: lease.id,
+        pages: Default::default(),
+    }));
+
+    let status = coordinator.get_status();
+
+    assert_eq!(status.queued, 3);
+    assert_eq!(status.crawled, 1);
+
+    // the pages budget leases the two pages left
+    let lease = coordinator.lease(&request);
+
+    assert_eq!(lease.links.len(), 2);
+    assert!(lease.links.iter().all(|link| link.depth == 1));
+
+    // the links of the pages unreported are queued again first
+    assert!(coordinator.report(Report {
+        lease: lease.id,
+        pages: vec![page(
+            &lease.links[0].url,
+            &["https://choosealicense.com/licenses/mit/"],
+        )],
+    }));
+
+    let status = coordinator.get_status();
+
+    assert_eq!(status.crawled, 2);
+    assert_eq!(status.queued, 2);
+
+    let retry = coordinator.lease(&request);
+
+    assert_eq!(retry.links.len(), 1);
+    assert_eq!(retry.links[0].url, lease.links[1].url);
+
+    assert!(coordinator.report(Report {
+        lease: retry.id,
+        pages: vec![page(&retry.links[0].url, &[])],
+    }));
+    assert!(coordinator.is_done());
+    assert!(coordinator.lease(&request).done);
+    assert_eq!(coordinator.get_results().len(), 3);
+
+    // the links of the leases expired are leased again
+    let coordinator = Coordinator::new(
+        "https://choosealicense.com/",
+        CrawlBudget {
+            lease_timeout: Duration::ZERO,
+            ..Default::default()
+        },
+    );
+    let lease = coordinator.lease(&request);
+    let retry = coordinator.lease(&request);
+
+    assert_eq!(retry.links.len(), 1);
+    assert!(!coordinator.report(Report {
+        lease: lease.id,
+        pages: vec![page("https://choosealicense.com/", &[])],
+    }));
+}
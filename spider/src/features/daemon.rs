@@ -0,0 +1,191 @@
+use crate::page::Page;
+use crate::utils::log;
+use crate::website::{CrawlControl, CrawlStatus, Website};
+use hashbrown::HashMap;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use tokio::sync::broadcast;
+
+/// A crawl started in the background.
+pub(crate) struct CrawlEntry {
+    /// The start url of the crawl.
+    pub(crate) url: String,
+    /// The control of the crawl shutting it down when cancelled.
+    control: CrawlControl,
+    /// The pages of the crawl subscribed by the streams. Closed once the crawl ended.
+    pages: broadcast::Receiver<Page>,
+    /// The status of the crawl once ended.
+    status: Mutex<String>,
+    /// The links visited by the crawl once ended.
+    links: Mutex<Vec<String>>,
+    /// The pages crawled.
+    page_count: AtomicU64,
+    /// The requests failed.
+    failures: AtomicU64,
+    /// The crawl ended.
+    finished: AtomicBool,
+    /// The crawl was cancelled.
+    cancelled: AtomicBool,
+}
+
+impl CrawlEntry {
+    /// Subscribe to the pages crawled from now on. The receiver is closed once the crawl ended.
+    pub(crate) fn subscribe(&self) -> broadcast::Receiver<Page> {
+        self.pages.resubscribe()
+    }
+
+    /// Get the status of the crawl, Active until the crawl ended.
+    pub(crate) fn get_status(&self) -> String {
+        match self.status.lock() {
+            Ok(status) if self.is_finished() => status.clone(),
+            _ => CrawlStatus::Active.to_string(),
+        }
+    }
+
+    /// Get the links visited by the crawl, empty until the crawl ended.
+    pub(crate) fn get_links(&self) -> Vec<String> {
+        match self.links.lock() {
+            Ok(links) => links.clone(),
+            _ => Default::default(),
+        }
+    }
+
+    /// Get the pages crawled.
+    pub(crate) fn get_pages(&self) -> u64 {
+        self.page_count.load(Ordering::Relaxed)
+    }
+
+    /// Get the requests failed.
+    pub(crate) fn get_failures(&self) -> u64 {
+        self.failures.load(Ordering::Relaxed)
+    }
+
+    /// The crawl ended.
+    pub(crate) fn is_finished(&self) -> bool {
+        self.finished.load(Ordering::Relaxed)
+    }
+
+    /// The crawl was cancelled.
+    pub(crate) fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Relaxed)
+    }
+
+    /// Cancel the crawl shutting down its requests in flight. False when the crawl ended or was
+    /// already cancelled.
+    pub(crate) fn cancel(&self) -> bool {
+        let cancelled = !self.is_finished() && !self.cancelled.swap(true, Ordering::Relaxed);
+
+        if cancelled {
+            self.control.shutdown();
+        }
+
+        cancelled
+    }
+}
+
+/// The crawls started in the background by their id.
+pub(crate) struct CrawlRegistry {
+    /// The crawls started by their id.
+    crawls: Mutex<HashMap<String, Arc<CrawlEntry>>>,
+    /// The id of the next crawl.
+    next_id: AtomicU64,
+    /// The pages buffered per crawl for the streams slower than the crawl.
+    pub(crate) capacity: usize,
+}
+
+impl Default for CrawlRegistry {
+    fn default() -> Self {
+        Self {
+            crawls: Default::default(),
+            next_id: AtomicU64::new(1),
+            capacity: 256,
+        }
+    }
+}
+
+impl CrawlRegistry {
+    /// Start the crawl of the website in a task of the runtime getting its id.
+    pub(crate) fn start(&self, mut website: Website) -> Option<String> {
+        let pages = website.subscribe(self.capacity)?;
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed).to_string();
+        let crawl = Arc::new(CrawlEntry {
+            url: website.get_domain().inner().to_string(),
+            control: website.get_control(),
+            pages: pages.resubscribe(),
+            status: Default::default(),
+            links: Default::default(),
+            page_count: AtomicU64::new(0),
+            failures: AtomicU64::new(0),
+            finished: AtomicBool::new(false),
+            cancelled: AtomicBool::new(false),
+        });
+
+        match self.crawls.lock() {
+            Ok(mut crawls) => {
+                crawls.insert(id.clone(), crawl.clone());
+            }
+            _ => return None,
+        }
+
+        let counter = crawl.clone();
+
+        tokio::spawn(async move {
+            let mut pages = pages;
+
+            loop {
+                match pages.recv().await {
+                    Ok(page) => {
+                        counter.page_count.fetch_add(1, Ordering::Relaxed);
+
+                        if page.is_failed() {
+                            counter.failures.fetch_add(1, Ordering::Relaxed);
+                        }
+                    }
+                    Err(broadcast::error::RecvError::Lagged(_)) => (),
+                    _ => break,
+                }
+            }
+        });
+
+        tokio::spawn(async move {
+            // the crawl cancelled before starting is not started as starting resumes the control
+            if !crawl.is_cancelled() {
+                log("daemon crawl started", &crawl.url);
+                website.crawl().await;
+            }
+
+            let stats = website.get_stats();
+
+            crawl.page_count.store(stats.pages, Ordering::Relaxed);
+            crawl.failures.store(stats.failures, Ordering::Relaxed);
+
+            match crawl.status.lock() {
+                Ok(mut status) => *status = website.get_status().to_string(),
+                _ => (),
+            }
+
+            match crawl.links.lock() {
+                Ok(mut links) => {
+                    *links = website
+                        .get_links()
+                        .iter()
+                        .map(|link| link.inner().to_string())
+                        .collect()
+                }
+                _ => (),
+            }
+
+            crawl.finished.store(true, Ordering::Relaxed);
+        });
+
+        Some(id)
+    }
+
+    /// Get the crawl of the id.
+    pub(crate) fn get(&self, id: &str) -> Option<Arc<CrawlEntry>> {
+        match self.crawls.lock() {
+            Ok(crawls) => crawls.get(id).cloned(),
+            _ => None,
+        }
+    }
+}
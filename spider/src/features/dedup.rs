@@ -0,0 +1,230 @@
+use crate::configuration::Deduplication;
+use crate::features::diff::get_digest;
+use crate::packages::scraper::Html;
+use hashbrown::HashMap;
+use sha2::{Digest, Sha256};
+use std::sync::Mutex;
+
+/// The words of the shingles of the SimHash.
+const SHINGLE_SIZE: usize = 3;
+
+/// The fingerprints of the text of a page.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Fingerprint {
+    /// The SHA-256 of the words of the text.
+    pub sha256: [u8; 32],
+    /// The SimHash of the shingles of the words of the text lowercase.
+    pub simhash: u64,
+}
+
+impl Fingerprint {
+    /// Fingerprint the words of the text. Returns `None` when the text has no words.
+    pub fn new(text: &str) -> Option<Self> {
+        let words: Vec<&str> = text.split_whitespace().collect();
+
+        if words.is_empty() {
+            return None;
+        }
+
+        let mut hasher = Sha256::new();
+
+        for (i, word) in words.iter().enumerate() {
+            if i > 0 {
+                hasher.update(b" ");
+            }
+            hasher.update(word.as_bytes());
+        }
+
+        Some(Self {
+            sha256: hasher.finalize().into(),
+            simhash: get_simhash(&words),
+        })
+    }
+
+    /// Fingerprint the text of the html without its head, scripts, and styles.
+    pub fn from_html(html: &Html) -> Option<Self> {
+        let mut text = String::new();
+
+        for node in html.tree.root().descendants() {
+            match node.value().as_text() {
+                Some(t)
+                    if node.ancestors().all(|ancestor| {
+                        ancestor.value().as_element().map_or(true, |element| {
+                            !matches!(
+                                element.name(),
+                                "head" | "script" | "style" | "noscript" | "template"
+                            )
+                        })
+                    }) =>
+                {
+                    text.push_str(t);
+                    text.push(' ');
+                }
+                _ => (),
+            }
+        }
+
+        Self::new(&text)
+    }
+
+    /// Get the count of the bits differing between the SimHash of the fingerprints.
+    pub fn distance(&self, other: &Self) -> u32 {
+        (self.simhash ^ other.simhash).count_ones()
+    }
+}
+
+/// Get the SimHash of the shingles of the words lowercase. The texts shorter than a shingle are a
+/// single shingle.
+pub fn get_simhash(words: &[&str]) -> u64 {
+    let words: Vec<String> = words.iter().map(|word| word.to_lowercase()).collect();
+    let shingles: Vec<String> = if words.len() < SHINGLE_SIZE {
+        vec![words.join(" ")]
+    } else {
+        words
+            .windows(SHINGLE_SIZE)
+            .map(|shingle| shingle.join(" "))
+            .collect()
+    };
+    let mut weights = [0i64; 64];
+
+    for shingle in shingles {
+        let hash = mix(get_digest(shingle.as_bytes()));
+
+        for (bit, weight) in weights.iter_mut().enumerate() {
+            if (hash >> bit) & 1 == 1 {
+                *weight += 1;
+            } else {
+                *weight -= 1;
+            }
+        }
+    }
+
+    weights
+        .iter()
+        .enumerate()
+        .filter(|(_, weight)| **weight > 0)
+        .fold(0, |simhash, (bit, _)| simhash | (1 << bit))
+}
+
+/// Mix the bits of the digest of a shingle for each bit to be set by half of the shingles.
+fn mix(mut hash: u64) -> u64 {
+    hash ^= hash >> 30;
+    hash = hash.wrapping_mul(0xbf58476d1ce4e5b9);
+    hash ^= hash >> 27;
+    hash = hash.wrapping_mul(0x94d049bb133111eb);
+    hash ^ (hash >> 31)
+}
+
+/// The fingerprints of the originals and the duplicates found.
+#[derive(Debug, Default)]
+struct Fingerprints {
+    /// The url of the original of the SHA-256.
+    exact: HashMap<[u8; 32], String>,
+    /// The SimHash of the originals with their url.
+    originals: Vec<(u64, String)>,
+    /// The originals of the bands of their SimHash.
+    bands: HashMap<(u32, u64), Vec<usize>>,
+    /// The url of the original of the duplicates.
+    duplicates: HashMap<String, String>,
+}
+
+/// The fingerprints of the pages crawled shared across the tasks of the crawl.
+#[derive(Debug, Default)]
+pub struct DuplicateStore {
+    /// The detection of the duplicates.
+    dedup: Deduplication,
+    /// The fingerprints of the originals and the duplicates found.
+    fingerprints: Mutex<Fingerprints>,
+}
+
+impl DuplicateStore {
+    /// Establish a new empty duplicate store.
+    pub fn new(dedup: Deduplication) -> Self {
+        Self {
+            dedup,
+            ..Default::default()
+        }
+    }
+
+    /// Get the detection of the duplicates.
+    pub fn get_dedup(&self) -> &Deduplication {
+        &self.dedup
+    }
+
+    /// Record the fingerprint of the page of the url. Returns the url of the original when the page
+    /// is a duplicate or a near duplicate of a page recorded before.
+    pub fn insert(&self, url: &str, fingerprint: &Fingerprint) -> Option<String> {
+        let mut fingerprints = self.fingerprints.lock().ok()?;
+        let bands = self.get_bands(fingerprint.simhash);
+        let original = match fingerprints.exact.get(&fingerprint.sha256) {
+            Some(original) => Some(original.clone()),
+            _ => bands
+                .iter()
+                .filter_map(|band| fingerprints.bands.get(band))
+                .flatten()
+                .map(|index| &fingerprints.originals[*index])
+                .find(|(simhash, _)| {
+                    (simhash ^ fingerprint.simhash).count_ones() <= self.dedup.max_distance
+                })
+                .map(|(_, original)| original.clone()),
+        };
+
+        match original {
+            // the page crawled again is its own original
+            Some(original) if original == url => None,
+            Some(original) => {
+                fingerprints.duplicates.insert(url.into(), original.clone());
+                Some(original)
+            }
+            _ => {
+                let index = fingerprints.originals.len();
+
+                fingerprints.exact.insert(fingerprint.sha256, url.into());
+                fingerprints
+                    .originals
+                    .push((fingerprint.simhash, url.into()));
+
+                for band in bands {
+                    fingerprints.bands.entry(band).or_default().push(index);
+                }
+
+                None
+            }
+        }
+    }
+
+    /// Get the url of the original of the duplicate urls.
+    pub fn get_duplicates(&self) -> HashMap<String, String> {
+        match self.fingerprints.lock() {
+            Ok(fingerprints) => fingerprints.duplicates.clone(),
+            _ => Default::default(),
+        }
+    }
+
+    /// Remove all the fingerprints and the duplicates found.
+    pub fn clear(&self) {
+        match self.fingerprints.lock() {
+            Ok(mut fingerprints) => *fingerprints = Default::default(),
+            _ => (),
+        }
+    }
+
+    /// Get the bands of the SimHash split in the max distance plus one bands.
+    fn get_bands(&self, simhash: u64) -> Vec<(u32, u64)> {
+        let count = (self.dedup.max_distance + 1).min(64);
+
+        (0..count)
+            .map(|i| {
+                let start = i * 64 / count;
+                let width = (i + 1) * 64 / count - start;
+                let mask = if width == 64 {
+                    u64::MAX
+                } else {
+                    (1 << width) - 1
+                };
+
+                (i, (simhash >> start) & mask)
+            })
+            .collect()
+    }
+}
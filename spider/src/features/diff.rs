@@ -0,0 +1,181 @@
+use crate::CaseInsensitiveString;
+use compact_str::CompactString;
+use hashbrown::{HashMap, HashSet};
+
+/// The normalization of the page content before hashing to ignore the parts changing on every
+/// request.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default)
+)]
+pub struct ContentNormalizer {
+    /// Ignore the script, style, and noscript elements. Defaults to true.
+    pub ignore_scripts: bool,
+    /// Ignore the values of the attributes like csrf tokens. Defaults to `nonce`.
+    pub ignore_attributes: Vec<CompactString>,
+    /// Ignore the digits to skip the timestamps and counters. Defaults to false.
+    pub ignore_digits: bool,
+    /// Collapse the whitespace. Defaults to true.
+    pub collapse_whitespace: bool,
+}
+
+impl Default for ContentNormalizer {
+    fn default() -> Self {
+        Self {
+            ignore_scripts: true,
+            ignore_attributes: vec!["nonce".into()],
+            ignore_digits: false,
+            collapse_whitespace: true,
+        }
+    }
+}
+
+impl ContentNormalizer {
+    /// Normalize the page content.
+    pub fn normalize(&self, content: &str) -> String {
+        let mut content = content.to_string();
+
+        if self.ignore_scripts {
+            for tag in ["script", "style", "noscript"] {
+                content = strip_elements(&content, tag);
+            }
+        }
+        for attribute in self.ignore_attributes.iter() {
+            content = strip_attribute(&content, attribute);
+        }
+        if self.ignore_digits {
+            content.retain(|c| !c.is_ascii_digit());
+        }
+        if self.collapse_whitespace {
+            content = content.split_whitespace().collect::<Vec<_>>().join(" ");
+        }
+
+        content
+    }
+
+    /// Get the digest of the page content normalized.
+    pub fn digest(&self, content: &[u8]) -> u64 {
+        get_digest(self.normalize(&String::from_utf8_lossy(content)).as_bytes())
+    }
+}
+
+/// Get the FNV-1a digest of the content stable across runs to persist.
+pub fn get_digest(content: &[u8]) -> u64 {
+    content.iter().fold(0xcbf29ce484222325, |digest, byte| {
+        (digest ^ *byte as u64).wrapping_mul(0x100000001b3)
+    })
+}
+
+/// Remove the elements of the tag with their content.
+fn strip_elements(content: &str, tag: &str) -> String {
+    let lowercase = content.to_ascii_lowercase();
+    let open = format!("<{}", tag);
+    let close = format!("</{}", tag);
+    let mut stripped = String::with_capacity(content.len());
+    let mut position = 0;
+
+    while let Some(start) = lowercase[position..].find(&open).map(|i| i + position) {
+        let end = start + open.len();
+
+        // skip the tags sharing the prefix like `<scripts>`
+        if !lowercase[end..].starts_with(|c: char| c == '>' || c.is_ascii_whitespace()) {
+            stripped.push_str(&content[position..end]);
+            position = end;
+            continue;
+        }
+
+        stripped.push_str(&content[position..start]);
+        position = match lowercase[end..].find(&close) {
+            Some(i) => match lowercase[end + i..].find('>') {
+                Some(j) => end + i + j + 1,
+                _ => content.len(),
+            },
+            _ => content.len(),
+        };
+    }
+    stripped.push_str(&content[position..]);
+
+    stripped
+}
+
+/// Remove the values of the attribute.
+fn strip_attribute(content: &str, attribute: &str) -> String {
+    let lowercase = content.to_ascii_lowercase();
+    let name = format!("{}=", attribute.to_ascii_lowercase());
+    let mut stripped = String::with_capacity(content.len());
+    let mut position = 0;
+
+    while let Some(start) = lowercase[position..].find(&name).map(|i| i + position) {
+        let end = start + name.len();
+        stripped.push_str(&content[position..end]);
+
+        // skip the attributes sharing the suffix like `data-nonce`
+        if !lowercase[..start].ends_with(|c: char| c.is_ascii_whitespace()) {
+            position = end;
+            continue;
+        }
+
+        position = match lowercase[end..].chars().next() {
+            Some(quote) if quote == '"' || quote == '\'' => {
+                match lowercase[end + 1..].find(quote) {
+                    Some(i) => end + 1 + i + 1,
+                    _ => content.len(),
+                }
+            }
+            _ => match lowercase[end..].find(|c: char| c == '>' || c.is_ascii_whitespace()) {
+                Some(i) => end + i,
+                _ => content.len(),
+            },
+        };
+    }
+    stripped.push_str(&content[position..]);
+
+    stripped
+}
+
+/// The changes of the pages between two crawls from their content digests.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct CrawlDiff {
+    /// The urls crawled in both crawls with a content changed.
+    pub changed: HashSet<CaseInsensitiveString>,
+    /// The urls crawled not found in the previous crawl.
+    pub added: HashSet<CaseInsensitiveString>,
+    /// The urls of the previous crawl not found in the crawl.
+    pub removed: HashSet<CaseInsensitiveString>,
+}
+
+impl CrawlDiff {
+    /// Compare the digests of the crawl with the digests of the previous crawl.
+    pub fn new(
+        previous: &HashMap<CaseInsensitiveString, u64>,
+        current: &HashMap<CaseInsensitiveString, u64>,
+    ) -> Self {
+        let mut diff = Self::default();
+
+        for (link, digest) in current.iter() {
+            match previous.get(link) {
+                Some(previous) if previous != digest => {
+                    diff.changed.insert(link.clone());
+                }
+                Some(_) => (),
+                _ => {
+                    diff.added.insert(link.clone());
+                }
+            }
+        }
+        for link in previous.keys() {
+            if !current.contains_key(link) {
+                diff.removed.insert(link.clone());
+            }
+        }
+
+        diff
+    }
+
+    /// No urls changed, added, or removed.
+    pub fn is_empty(&self) -> bool {
+        self.changed.is_empty() && self.added.is_empty() && self.removed.is_empty()
+    }
+}
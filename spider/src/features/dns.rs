@@ -0,0 +1,130 @@
+use crate::configuration::DnsConfig;
+use crate::utils::log;
+use reqwest::dns::{Addrs, Name, Resolve, Resolving};
+use std::net::{IpAddr, SocketAddr};
+use std::sync::Arc;
+use std::time::Instant;
+use tokio::sync::OnceCell;
+use trust_dns_resolver::config::{NameServerConfig, Protocol, ResolverConfig, ResolverOpts};
+use trust_dns_resolver::TokioAsyncResolver;
+use url::Url;
+
+/// The resolver of the hosts with the nameservers or the DNS-over-HTTPS endpoint.
+#[derive(Clone)]
+pub struct DnsResolver {
+    /// The async resolver caching the lookups. None when the endpoint can not be resolved.
+    resolver: Arc<OnceCell<Option<TokioAsyncResolver>>>,
+    /// The DNS-over-HTTPS endpoint resolved on the first lookup.
+    doh: Option<Url>,
+}
+
+impl DnsResolver {
+    /// Establish the resolver of the config. Returns `None` without nameservers or an endpoint to
+    /// keep the system resolver.
+    pub fn new(config: &DnsConfig) -> Option<Self> {
+        match &config.doh {
+            Some(doh) => {
+                let url = Url::parse(doh).ok()?;
+                url.host_str()?;
+
+                Some(Self {
+                    resolver: Default::default(),
+                    doh: Some(url),
+                })
+            }
+            _ if config.nameservers.is_empty() => None,
+            _ => {
+                let mut resolver_config = ResolverConfig::new();
+
+                for socket_addr in config.nameservers.iter() {
+                    resolver_config
+                        .add_name_server(NameServerConfig::new(*socket_addr, Protocol::Udp));
+                    resolver_config
+                        .add_name_server(NameServerConfig::new(*socket_addr, Protocol::Tcp));
+                }
+
+                let resolver = TokioAsyncResolver::tokio(resolver_config, ResolverOpts::default());
+
+                Some(Self {
+                    resolver: Arc::new(OnceCell::new_with(Some(Some(resolver)))),
+                    doh: None,
+                })
+            }
+        }
+    }
+
+    /// Get the async resolver resolving the host of the endpoint once with the system resolver.
+    async fn get_resolver(&self) -> Option<&TokioAsyncResolver> {
+        self.resolver
+            .get_or_init(|| async {
+                let url = self.doh.as_ref()?;
+                let host = url.host_str()?;
+                let mut resolver_config = ResolverConfig::new();
+
+                match tokio::net::lookup_host((host, url.port_or_known_default().unwrap_or(443)))
+                    .await
+                {
+                    Ok(socket_addrs) => {
+                        for socket_addr in socket_addrs {
+                            let mut name_server =
+                                NameServerConfig::new(socket_addr, Protocol::Https);
+                            name_server.tls_dns_name = Some(host.into());
+                            resolver_config.add_name_server(name_server);
+                        }
+                    }
+                    Err(e) => log("dns endpoint lookup failed", e.to_string()),
+                }
+
+                if resolver_config.name_servers().is_empty() {
+                    None
+                } else {
+                    Some(TokioAsyncResolver::tokio(
+                        resolver_config,
+                        ResolverOpts::default(),
+                    ))
+                }
+            })
+            .await
+            .as_ref()
+    }
+
+    /// Get the addresses of the host with the time the records expire. The system resolver
+    /// looks up the host when the endpoint can not be resolved.
+    pub async fn lookup(&self, host: &str) -> std::io::Result<(Vec<IpAddr>, Option<Instant>)> {
+        match self.get_resolver().await {
+            Some(resolver) => {
+                let lookup = resolver.lookup_ip(host).await?;
+
+                Ok((lookup.iter().collect(), Some(lookup.valid_until())))
+            }
+            _ => Ok((
+                tokio::net::lookup_host((host, 0))
+                    .await?
+                    .map(|socket_addr| socket_addr.ip())
+                    .collect(),
+                None,
+            )),
+        }
+    }
+}
+
+impl std::fmt::Debug for DnsResolver {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("DnsResolver").finish()
+    }
+}
+
+impl Resolve for DnsResolver {
+    fn resolve(&self, name: Name) -> Resolving {
+        let resolver = self.clone();
+
+        Box::pin(async move {
+            let (addrs, _) = resolver.lookup(name.as_str()).await?;
+            let addrs: Vec<SocketAddr> =
+                addrs.into_iter().map(|ip| SocketAddr::new(ip, 0)).collect();
+            let addrs: Addrs = Box::new(addrs.into_iter());
+
+            Ok(addrs)
+        })
+    }
+}
@@ -0,0 +1,199 @@
+#[cfg(feature = "dns")]
+use crate::features::dns::DnsResolver;
+use compact_str::CompactString;
+use hashbrown::{HashMap, HashSet};
+use reqwest::dns::{Addrs, Name, Resolve, Resolving};
+use std::net::{IpAddr, SocketAddr, ToSocketAddrs};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// The addresses of a host cached until they expire.
+#[derive(Debug, Clone)]
+struct DnsEntry {
+    /// The addresses of the host.
+    addrs: Vec<IpAddr>,
+    /// The time the addresses expire.
+    expires_at: Instant,
+}
+
+/// The addresses of the hosts shared across the clients of the crawl.
+#[derive(Debug)]
+pub struct DnsCache {
+    /// The max duration of the addresses cached.
+    ttl: Duration,
+    #[cfg(feature = "dns")]
+    /// The resolver of the nameservers or the DNS-over-HTTPS endpoint instead of the system.
+    resolver: Option<DnsResolver>,
+    /// The addresses cached per host.
+    entries: Mutex<HashMap<CompactString, DnsEntry>>,
+    /// The hosts being prefetched.
+    pending: Mutex<HashSet<CompactString>>,
+    /// The lookups sent to the resolver.
+    lookups: AtomicUsize,
+}
+
+impl DnsCache {
+    /// Establish a new empty cache of the hosts for the duration.
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            #[cfg(feature = "dns")]
+            resolver: None,
+            entries: Default::default(),
+            pending: Default::default(),
+            lookups: Default::default(),
+        }
+    }
+
+    #[cfg(feature = "dns")]
+    /// Set the resolver of the lookups. Defaults to the system resolver.
+    pub fn with_resolver(&mut self, resolver: Option<DnsResolver>) -> &mut Self {
+        self.resolver = resolver;
+        self
+    }
+
+    /// Get the addresses cached of the host not expired.
+    pub fn get(&self, host: &str) -> Option<Vec<IpAddr>> {
+        match self.entries.lock() {
+            Ok(entries) => match entries.get(host.to_ascii_lowercase().as_str()) {
+                Some(entry) if entry.expires_at > Instant::now() => Some(entry.addrs.clone()),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    /// Cache the addresses of the host until they expire capped with the duration of the cache.
+    pub fn insert(&self, host: &str, addrs: Vec<IpAddr>, valid_until: Option<Instant>) {
+        let max = Instant::now() + self.ttl;
+        let expires_at = match valid_until {
+            Some(valid_until) if valid_until < max => valid_until,
+            _ => max,
+        };
+
+        match self.entries.lock() {
+            Ok(mut entries) => {
+                entries.insert(
+                    host.to_ascii_lowercase().into(),
+                    DnsEntry { addrs, expires_at },
+                );
+            }
+            _ => (),
+        }
+    }
+
+    /// Get the count of the lookups sent to the resolver on the misses of the cache.
+    pub fn get_lookups(&self) -> usize {
+        self.lookups.load(Ordering::Relaxed)
+    }
+
+    /// Get the count of the hosts cached including the expired hosts.
+    pub fn len(&self) -> usize {
+        self.entries
+            .lock()
+            .map(|entries| entries.len())
+            .unwrap_or_default()
+    }
+
+    /// The cache has no hosts.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Remove all the hosts cached.
+    pub fn clear(&self) {
+        match self.entries.lock() {
+            Ok(mut entries) => entries.clear(),
+            _ => (),
+        }
+    }
+
+    /// Get the addresses of the host from the cache or the resolver caching them.
+    pub async fn lookup(&self, host: &str) -> std::io::Result<Vec<IpAddr>> {
+        match self.get(host) {
+            Some(addrs) => return Ok(addrs),
+            _ => (),
+        }
+
+        self.lookups.fetch_add(1, Ordering::Relaxed);
+
+        #[cfg(feature = "dns")]
+        let (addrs, valid_until) = match &self.resolver {
+            Some(resolver) => resolver.lookup(host).await?,
+            _ => (lookup_system(host).await?, None),
+        };
+        #[cfg(not(feature = "dns"))]
+        let (addrs, valid_until) = (lookup_system(host).await?, None);
+
+        self.insert(host, addrs.clone(), valid_until);
+
+        Ok(addrs)
+    }
+
+    /// Resolve the host in the background when it is not cached or being resolved.
+    pub fn prefetch(self: &Arc<Self>, host: &str) {
+        if host.parse::<IpAddr>().is_ok() || self.get(host).is_some() {
+            return;
+        }
+
+        let host: CompactString = host.to_ascii_lowercase().into();
+
+        match self.pending.lock() {
+            Ok(mut pending) if pending.insert(host.clone()) => (),
+            _ => return,
+        }
+
+        let dns_cache = self.clone();
+
+        tokio::spawn(async move {
+            let _ = dns_cache.lookup(&host).await;
+
+            match dns_cache.pending.lock() {
+                Ok(mut pending) => {
+                    pending.remove(&host);
+                }
+                _ => (),
+            }
+        });
+    }
+
+    /// Get the resolver of the clients looking up the hosts with the cache.
+    pub fn get_resolver(self: &Arc<Self>) -> Arc<impl Resolve> {
+        Arc::new(CachedResolver(self.clone()))
+    }
+}
+
+/// Resolve the host with the system resolver.
+pub(crate) async fn lookup_system(host: &str) -> std::io::Result<Vec<IpAddr>> {
+    let host = host.to_string();
+
+    tokio::task::spawn_blocking(move || {
+        (host.as_str(), 0)
+            .to_socket_addrs()
+            .map(|addrs| addrs.map(|addr| addr.ip()).collect())
+    })
+    .await
+    .map_err(|e| std::io::Error::new(std::io::ErrorKind::Other, e))?
+}
+
+/// The resolver of the clients with the cache.
+struct CachedResolver(Arc<DnsCache>);
+
+impl Resolve for CachedResolver {
+    fn resolve(&self, name: Name) -> Resolving {
+        let dns_cache = self.0.clone();
+
+        Box::pin(async move {
+            let addrs: Vec<SocketAddr> = dns_cache
+                .lookup(name.as_str())
+                .await?
+                .into_iter()
+                .map(|ip| SocketAddr::new(ip, 0))
+                .collect();
+            let addrs: Addrs = Box::new(addrs.into_iter());
+
+            Ok(addrs)
+        })
+    }
+}
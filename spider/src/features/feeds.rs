@@ -0,0 +1,195 @@
+use crate::page::convert_abs_path;
+use reqwest::Client;
+use std::sync::Mutex;
+use url::Url;
+use xml::reader::{EventReader, XmlEvent};
+
+/// The mime types of the feeds linked from the pages.
+pub const FEED_TYPES: [&str; 2] = ["application/rss+xml", "application/atom+xml"];
+
+/// A feed parsed.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Feed {
+    /// The url of the feed.
+    pub url: String,
+    /// The title of the channel or the feed.
+    pub title: Option<String>,
+    /// The items or the entries with a link in order.
+    pub items: Vec<FeedItem>,
+}
+
+/// An item or an entry of a feed.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct FeedItem {
+    /// The absolute url of the link of the item.
+    pub url: String,
+    /// The title of the item.
+    pub title: Option<String>,
+    /// The publication or the update date of the item as written in the feed.
+    pub published: Option<String>,
+}
+
+impl Feed {
+    /// Parse the RSS 2.0, RSS 1.0, or Atom feed at the url. Returns `None` when the content is not
+    /// a feed.
+    pub fn parse(url: &Url, content: &[u8]) -> Option<Self> {
+        let mut feed: Option<Feed> = None;
+        let mut item: Option<FeedItem> = None;
+        let mut text = String::new();
+
+        for event in EventReader::new(content) {
+            match event {
+                Ok(XmlEvent::StartElement {
+                    name, attributes, ..
+                }) => {
+                    text.clear();
+
+                    match (name.local_name.as_str(), feed.is_some()) {
+                        ("rss" | "RDF" | "feed", false) => {
+                            feed = Some(Feed {
+                                url: url.as_str().into(),
+                                ..Default::default()
+                            })
+                        }
+                        (_, false) => return None,
+                        ("item" | "entry", true) => item = Some(Default::default()),
+                        // the atom links are set with the href of the alternate link
+                        ("link", true) => {
+                            let attr = |name: &str| {
+                                attributes
+                                    .iter()
+                                    .find(|attribute| attribute.name.local_name == name)
+                                    .map(|attribute| attribute.value.trim())
+                            };
+
+                            match (&mut item, attr("href"), attr("rel")) {
+                                (Some(item), Some(href), None | Some("alternate"))
+                                    if item.url.is_empty() =>
+                                {
+                                    item.url = convert_abs_path(url, href).as_str().into();
+                                }
+                                _ => (),
+                            }
+                        }
+                        _ => (),
+                    }
+                }
+                Ok(XmlEvent::Characters(characters)) | Ok(XmlEvent::CData(characters)) => {
+                    text.push_str(&characters)
+                }
+                Ok(XmlEvent::EndElement { name }) => {
+                    let value = text.trim();
+
+                    match (name.local_name.as_str(), &mut item, &mut feed) {
+                        ("item" | "entry", Some(_), Some(feed)) => match item.take() {
+                            Some(item) if !item.url.is_empty() => feed.items.push(item),
+                            _ => (),
+                        },
+                        ("title", Some(item), _) if item.title.is_none() => {
+                            item.title = Some(value.into())
+                        }
+                        ("title", None, Some(feed)) if feed.title.is_none() => {
+                            feed.title = Some(value.into())
+                        }
+                        ("link", Some(item), _) if item.url.is_empty() && !value.is_empty() => {
+                            item.url = convert_abs_path(url, value).as_str().into()
+                        }
+                        ("pubDate" | "published" | "updated" | "date", Some(item), _)
+                            if item.published.is_none() =>
+                        {
+                            item.published = Some(value.into())
+                        }
+                        _ => (),
+                    }
+
+                    text.clear();
+                }
+                Err(_) => break,
+                _ => (),
+            }
+        }
+
+        feed
+    }
+}
+
+/// Fetch and parse the feed at the url. Returns `None` when the request fails or the content is
+/// not a feed.
+pub async fn fetch_feed(client: &Client, url: &str) -> Option<Feed> {
+    let response = crate::utils::send_request(url, client, Default::default())
+        .await
+        .ok()?;
+
+    if !response.status().is_success() {
+        return None;
+    }
+
+    Feed::parse(
+        &Url::parse(url).ok()?,
+        &crate::utils::get_body_bytes(response).await.ok()?,
+    )
+}
+
+/// The feeds found and parsed shared across the tasks of the crawl.
+#[derive(Debug, Default)]
+pub struct FeedStore {
+    /// The urls of the feeds found in the order of the pages crawled.
+    urls: Mutex<Vec<String>>,
+    /// The feeds fetched and parsed.
+    feeds: Mutex<Vec<Feed>>,
+}
+
+impl FeedStore {
+    /// Establish a new empty feed store.
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Record the url of a feed found. Returns `true` when the url was not found before.
+    pub fn insert_url(&self, url: &str) -> bool {
+        match self.urls.lock() {
+            Ok(mut urls) if !urls.iter().any(|u| u == url) => {
+                urls.push(url.into());
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Record a feed parsed.
+    pub fn push(&self, feed: Feed) {
+        match self.feeds.lock() {
+            Ok(mut feeds) => feeds.push(feed),
+            _ => (),
+        }
+    }
+
+    /// Get the urls of the feeds found.
+    pub fn get_urls(&self) -> Vec<String> {
+        match self.urls.lock() {
+            Ok(urls) => urls.clone(),
+            _ => Default::default(),
+        }
+    }
+
+    /// Get the feeds fetched and parsed.
+    pub fn get_feeds(&self) -> Vec<Feed> {
+        match self.feeds.lock() {
+            Ok(feeds) => feeds.clone(),
+            _ => Default::default(),
+        }
+    }
+
+    /// Remove all the feeds found and parsed.
+    pub fn clear(&self) {
+        match self.urls.lock() {
+            Ok(mut urls) => urls.clear(),
+            _ => (),
+        }
+
+        match self.feeds.lock() {
+            Ok(mut feeds) => feeds.clear(),
+            _ => (),
+        }
+    }
+}
@@ -0,0 +1,866 @@
+use crate::configuration::{CrawlStrategy, FrontierSpill};
+use crate::utils::log;
+use crate::CaseInsensitiveString;
+use hashbrown::{HashMap, HashSet};
+use std::collections::{BTreeMap, BinaryHeap, VecDeque};
+use std::fs::{File, OpenOptions};
+use std::io::{BufRead, BufReader, BufWriter, Seek, SeekFrom, Write};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use url::Url;
+
+/// A link queued to crawl with the priority strategy.
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) struct QueuedLink {
+    /// The priority of the link, the highest is popped first.
+    priority: u32,
+    /// The order the link was queued in to pop the links queued first on ties.
+    order: std::cmp::Reverse<usize>,
+    /// The link hops from the start url.
+    depth: usize,
+    /// The link to crawl.
+    link: CaseInsensitiveString,
+}
+
+impl Ord for QueuedLink {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.priority
+            .cmp(&other.priority)
+            .then_with(|| self.order.cmp(&other.order))
+    }
+}
+
+impl PartialOrd for QueuedLink {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// The max links popped per batch from a shared frontier when the concurrency is set from the cpus
+/// available.
+const SHARED_FRONTIER_BATCH: usize = 100;
+
+/// The links queued to crawl with their depth popped in batches ordered by the crawl strategy.
+#[derive(Debug, Default)]
+pub(crate) struct Frontier {
+    /// The order to pop the links.
+    pub(crate) strategy: CrawlStrategy,
+    /// The max depth of the links queued. Use 0 to queue the links of any depth.
+    pub(crate) depth: usize,
+    /// The max links popped per batch with the priority strategy. Use 0 to pop all the links.
+    pub(crate) batch: usize,
+    /// The links found per page with their depth in the order to pop for breadth and depth first.
+    pub(crate) groups: VecDeque<(usize, HashSet<CaseInsensitiveString>)>,
+    /// The links queued with the priority strategy.
+    pub(crate) queue: BinaryHeap<QueuedLink>,
+    /// The count of links queued with the priority strategy.
+    pub(crate) queued: usize,
+    /// The callback scoring the links with their depth and anchor text for the priority strategy.
+    pub(crate) priority: Option<fn(&str, usize, &str) -> u32>,
+    /// The callback dropping, rewriting, or prioritizing the links found before they are queued.
+    pub(crate) on_link_found:
+        Option<fn(CaseInsensitiveString, usize) -> Option<(CaseInsensitiveString, Option<u32>)>>,
+    /// The links queued or in flight with their depth until fetched to resume the crawl from. The
+    /// links spilled are not pending until read back.
+    pub(crate) pending: HashMap<CaseInsensitiveString, usize>,
+    /// The count of the links in the groups.
+    pub(crate) len: usize,
+    /// The segments of the links spilled over the window kept in memory.
+    pub(crate) spill: Option<FrontierSegments>,
+    /// The frontier shared with the crawls of other processes replacing the links queued in memory.
+    pub(crate) shared: Option<Arc<dyn SharedFrontier>>,
+}
+
+impl Frontier {
+    /// Queue the links found at the depth with their anchor texts through the link found callback
+    /// dropping, rewriting, or prioritizing the links when set.
+    pub(crate) fn push(
+        &mut self,
+        links: HashSet<CaseInsensitiveString>,
+        depth: usize,
+        anchor_texts: &HashMap<CaseInsensitiveString, String>,
+    ) {
+        match self.on_link_found {
+            Some(on_link_found) => {
+                let mut priorities = HashMap::new();
+                let links = links
+                    .into_iter()
+                    .filter_map(|link| match on_link_found(link, depth) {
+                        Some((link, Some(priority))) => {
+                            priorities.insert(link.clone(), priority);
+                            Some(link)
+                        }
+                        found => found.map(|(link, _)| link),
+                    })
+                    .collect();
+
+                self.enqueue(links, depth, anchor_texts, &priorities);
+            }
+            _ => self.enqueue(links, depth, anchor_texts, &Default::default()),
+        }
+    }
+
+    /// Queue the links at the depth with their anchor texts and the priorities set by the link
+    /// found callback skipping the links over the max depth.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "frontier_enqueue",
+            level = "trace",
+            skip_all,
+            fields(links = links.len(), depth, queued = self.len)
+        )
+    )]
+    pub(crate) fn enqueue(
+        &mut self,
+        links: HashSet<CaseInsensitiveString>,
+        depth: usize,
+        anchor_texts: &HashMap<CaseInsensitiveString, String>,
+        priorities: &HashMap<CaseInsensitiveString, u32>,
+    ) {
+        if links.is_empty() || (self.depth > 0 && depth > self.depth) {
+            return;
+        }
+
+        match &self.shared {
+            Some(shared) => {
+                let links: Vec<SpilledLink> = links
+                    .into_iter()
+                    .map(|link| SpilledLink {
+                        priority: self.get_link_priority(&link, depth, anchor_texts, priorities),
+                        depth,
+                        link,
+                    })
+                    .collect();
+
+                shared.push(&links);
+                return;
+            }
+            _ => (),
+        }
+
+        for link in links.iter() {
+            self.pending.entry(link.clone()).or_insert(depth);
+        }
+
+        match self.strategy {
+            CrawlStrategy::BreadthFirst => {
+                let position = self
+                    .groups
+                    .iter()
+                    .rposition(|group| group.0 <= depth)
+                    .map_or(0, |position| position + 1);
+
+                self.len += links.len();
+                self.groups.insert(position, (depth, links));
+            }
+            CrawlStrategy::DepthFirst => {
+                self.len += links.len();
+                self.groups.push_back((depth, links));
+            }
+            CrawlStrategy::Priority => {
+                for link in links {
+                    let priority = self.get_link_priority(&link, depth, anchor_texts, priorities);
+
+                    self.queued += 1;
+                    self.queue.push(QueuedLink {
+                        priority,
+                        order: std::cmp::Reverse(self.queued),
+                        depth,
+                        link,
+                    });
+                }
+            }
+        }
+
+        self.spill_over();
+    }
+
+    /// Get the priority of the link by the crawl strategy. Breadth-first pops the shallowest links
+    /// first and depth-first the deepest links first in the shared frontier.
+    fn get_link_priority(
+        &self,
+        link: &CaseInsensitiveString,
+        depth: usize,
+        anchor_texts: &HashMap<CaseInsensitiveString, String>,
+        priorities: &HashMap<CaseInsensitiveString, u32>,
+    ) -> u32 {
+        match self.strategy {
+            CrawlStrategy::BreadthFirst => u32::MAX - depth.min(u32::MAX as usize) as u32,
+            CrawlStrategy::DepthFirst => depth.min(u32::MAX as usize) as u32,
+            CrawlStrategy::Priority => match (priorities.get(link), self.priority) {
+                (Some(priority), _) => *priority,
+                (_, Some(priority)) => priority(
+                    link.inner(),
+                    depth,
+                    anchor_texts.get(link).map_or("", |text| text.as_str()),
+                ),
+                _ => get_priority(link),
+            },
+        }
+    }
+
+    /// Spill the links queued over the window to the segments. Breadth-first spills the deepest
+    /// links, depth-first the bottom of the stack, and priority the lowest priorities down to half
+    /// of the window. The links are kept in memory when the segments cannot be written.
+    fn spill_over(&mut self) {
+        let window = match &self.spill {
+            Some(spill) => spill.get_window(),
+            _ => return,
+        };
+
+        match self.strategy {
+            CrawlStrategy::BreadthFirst => {
+                while self.len > window {
+                    let (depth, links) = match self.groups.pop_back() {
+                        Some(group) => group,
+                        _ => break,
+                    };
+                    let spilled: Vec<SpilledLink> = links
+                        .iter()
+                        .map(|link| SpilledLink {
+                            link: link.clone(),
+                            depth,
+                            priority: 0,
+                        })
+                        .collect();
+
+                    match self
+                        .spill
+                        .as_mut()
+                        .map(|spill| spill.push_depth(depth, &spilled))
+                    {
+                        Some(Ok(_)) => {
+                            self.len -= links.len();
+
+                            for link in links.iter() {
+                                self.pending.remove(link);
+                            }
+                        }
+                        _ => {
+                            self.groups.push_back((depth, links));
+                            break;
+                        }
+                    }
+                }
+            }
+            CrawlStrategy::DepthFirst if self.len > window => {
+                let mut groups = Vec::new();
+                let mut spilled = Vec::new();
+
+                // the last page queued is kept to pop next
+                while self.len > window / 2 && self.groups.len() > 1 {
+                    match self.groups.pop_front() {
+                        Some((depth, links)) => {
+                            self.len -= links.len();
+                            spilled.extend(links.iter().map(|link| SpilledLink {
+                                link: link.clone(),
+                                depth,
+                                priority: 0,
+                            }));
+                            groups.push((depth, links));
+                        }
+                        _ => break,
+                    }
+                }
+
+                if spilled.is_empty() {
+                    return;
+                }
+
+                match self
+                    .spill
+                    .as_mut()
+                    .map(|spill| spill.push_segment(&spilled))
+                {
+                    Some(Ok(_)) => {
+                        for link in spilled.iter() {
+                            self.pending.remove(&link.link);
+                        }
+                    }
+                    _ => {
+                        for (depth, links) in groups.into_iter().rev() {
+                            self.len += links.len();
+                            self.groups.push_front((depth, links));
+                        }
+                    }
+                }
+            }
+            CrawlStrategy::Priority if self.queue.len() > window => {
+                let mut queued = std::mem::take(&mut self.queue).into_sorted_vec();
+                let kept = queued.split_off(queued.len() - window / 2);
+                let spilled: Vec<SpilledLink> = queued
+                    .iter()
+                    .map(|queued| SpilledLink {
+                        link: queued.link.clone(),
+                        depth: queued.depth,
+                        priority: queued.priority,
+                    })
+                    .collect();
+
+                match self
+                    .spill
+                    .as_mut()
+                    .map(|spill| spill.push_segment(&spilled))
+                {
+                    Some(Ok(_)) => {
+                        for link in spilled.iter() {
+                            self.pending.remove(&link.link);
+                        }
+                        self.queue = kept.into();
+                    }
+                    _ => {
+                        queued.extend(kept);
+                        self.queue = queued.into();
+                    }
+                }
+            }
+            _ => (),
+        }
+    }
+
+    /// Read back the links spilled of the shallowest depth for breadth-first or of the last segment
+    /// spilled recording them as pending. The spillover is stopped when the segments cannot be read.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "frontier_reload", level = "debug", skip_all)
+    )]
+    fn reload(&mut self) -> Vec<SpilledLink> {
+        let links = match (&mut self.spill, self.strategy) {
+            (Some(spill), CrawlStrategy::BreadthFirst) => spill.pop_depth(),
+            (Some(spill), _) => spill.pop_segment(),
+            _ => return Default::default(),
+        };
+
+        match links {
+            Ok(links) => {
+                for link in links.iter() {
+                    self.pending.entry(link.link.clone()).or_insert(link.depth);
+                }
+
+                #[cfg(feature = "tracing")]
+                tracing::debug!(links = links.len(), "frontier reloaded");
+
+                links
+            }
+            Err(err) => {
+                log("frontier spill error: ", err.to_string());
+                self.spill = None;
+                Default::default()
+            }
+        }
+    }
+
+    /// Read back all the links spilled as pending to resume the crawl from.
+    pub(crate) fn drain_spill(&mut self) {
+        match &mut self.spill {
+            Some(spill) => {
+                for link in spill.drain() {
+                    self.pending.entry(link.link).or_insert(link.depth);
+                }
+            }
+            _ => (),
+        }
+    }
+
+    /// Pop the next batch of links to crawl with their depth. Breadth-first pops all the links of
+    /// the shallowest depth and depth-first pops the links of the last page queued.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "frontier_pop",
+            level = "trace",
+            skip_all,
+            fields(queued = self.len)
+        )
+    )]
+    pub(crate) fn pop(&mut self) -> Vec<(CaseInsensitiveString, usize)> {
+        match &self.shared {
+            Some(shared) => {
+                let links = shared.pop(if self.batch == 0 {
+                    SHARED_FRONTIER_BATCH
+                } else {
+                    self.batch
+                });
+
+                return links
+                    .into_iter()
+                    .map(|link| {
+                        self.pending.insert(link.link.clone(), link.depth);
+                        (link.link, link.depth)
+                    })
+                    .collect();
+            }
+            _ => (),
+        }
+
+        match self.strategy {
+            CrawlStrategy::BreadthFirst => {
+                // the links spilled are popped after the links in memory of their depth
+                let spilled = match (
+                    self.groups.front(),
+                    self.spill.as_ref().and_then(|spill| spill.get_min_depth()),
+                ) {
+                    (Some(group), Some(depth)) => depth < group.0,
+                    (None, Some(_)) => true,
+                    _ => false,
+                };
+
+                if spilled {
+                    return self
+                        .reload()
+                        .into_iter()
+                        .map(|link| (link.link, link.depth))
+                        .collect();
+                }
+
+                let mut batch = Vec::new();
+
+                while let Some((depth, links)) = self.groups.pop_front() {
+                    self.len -= links.len();
+                    batch.extend(links.into_iter().map(|link| (link, depth)));
+
+                    if self.groups.front().map_or(true, |group| group.0 != depth) {
+                        break;
+                    }
+                }
+
+                batch
+            }
+            CrawlStrategy::DepthFirst => {
+                if self.groups.is_empty() {
+                    // the links of a page spilled are consecutive with their depth
+                    for link in self.reload() {
+                        match self.groups.back_mut() {
+                            Some((depth, links)) if *depth == link.depth => {
+                                links.insert(link.link);
+                            }
+                            _ => {
+                                self.groups
+                                    .push_back((link.depth, HashSet::from([link.link])));
+                            }
+                        }
+                        self.len += 1;
+                    }
+                }
+
+                match self.groups.pop_back() {
+                    Some((depth, links)) => {
+                        self.len -= links.len();
+                        links.into_iter().map(|link| (link, depth)).collect()
+                    }
+                    _ => Default::default(),
+                }
+            }
+            CrawlStrategy::Priority => {
+                if self.queue.is_empty() {
+                    for link in self.reload() {
+                        self.queued += 1;
+                        self.queue.push(QueuedLink {
+                            priority: link.priority,
+                            order: std::cmp::Reverse(self.queued),
+                            depth: link.depth,
+                            link: link.link,
+                        });
+                    }
+                }
+
+                let size = if self.batch == 0 {
+                    self.queue.len()
+                } else {
+                    self.batch.min(self.queue.len())
+                };
+
+                (0..size)
+                    .filter_map(|_| self.queue.pop())
+                    .map(|queued| (queued.link, queued.depth))
+                    .collect()
+            }
+        }
+    }
+
+    /// No links are queued.
+    pub(crate) fn is_empty(&self) -> bool {
+        self.groups.is_empty()
+            && self.queue.is_empty()
+            && self.spill.as_ref().map_or(true, |spill| spill.is_empty())
+            && self
+                .shared
+                .as_ref()
+                .map_or(true, |shared| shared.is_empty())
+    }
+}
+
+/// Get the priority of the link, the links with the fewest path segments have the highest priority.
+fn get_priority(link: &CaseInsensitiveString) -> u32 {
+    let segments = match Url::parse(link.inner()) {
+        Ok(url) => url
+            .path_segments()
+            .map_or(0, |segments| segments.filter(|s| !s.is_empty()).count()),
+        _ => 0,
+    };
+
+    u32::MAX - segments.min(u32::MAX as usize) as u32
+}
+
+/// The count of the segments created by the process to name them uniquely.
+static SEGMENTS: AtomicUsize = AtomicUsize::new(0);
+
+/// A link spilled with its depth and its priority.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct SpilledLink {
+    /// The link to crawl.
+    pub link: CaseInsensitiveString,
+    /// The link hops from the start url.
+    pub depth: usize,
+    /// The priority of the link with the priority strategy.
+    pub priority: u32,
+}
+
+/// An append-only file of the links spilled read back in the order appended.
+#[derive(Debug)]
+pub struct Segment {
+    /// The path of the segment file.
+    path: PathBuf,
+    /// The writer appending to the segment file.
+    writer: BufWriter<File>,
+    /// The offset of the links not read.
+    offset: u64,
+    /// The count of the links not read.
+    len: usize,
+}
+
+impl Segment {
+    /// Create a new empty segment file in the directory.
+    pub fn new(dir: &std::path::Path) -> std::io::Result<Self> {
+        let path = dir.join(format!(
+            "frontier-{}-{}.seg",
+            std::process::id(),
+            SEGMENTS.fetch_add(1, Ordering::Relaxed)
+        ));
+        // the links are appended to the end whatever the offset read of the file shared
+        let file = OpenOptions::new()
+            .create(true)
+            .read(true)
+            .append(true)
+            .open(&path)?;
+
+        file.set_len(0)?;
+
+        Ok(Self {
+            path,
+            writer: BufWriter::new(file),
+            offset: 0,
+            len: 0,
+        })
+    }
+
+    /// Append the links to the segment one per line as `priority depth link`.
+    pub fn append<'a>(
+        &mut self,
+        links: impl IntoIterator<Item = &'a SpilledLink>,
+    ) -> std::io::Result<()> {
+        for link in links {
+            writeln!(
+                self.writer,
+                "{} {} {}",
+                link.priority,
+                link.depth,
+                link.link.inner()
+            )?;
+            self.len += 1;
+        }
+
+        Ok(())
+    }
+
+    /// Read the next links appended up to the max. The segment file is truncated once all its links
+    /// are read.
+    pub fn read(&mut self, max: usize) -> std::io::Result<Vec<SpilledLink>> {
+        self.writer.flush()?;
+
+        let mut file = self.writer.get_ref().try_clone()?;
+        file.seek(SeekFrom::Start(self.offset))?;
+
+        let mut reader = BufReader::new(file);
+        let mut links = Vec::new();
+        let mut line = String::new();
+
+        while links.len() < max && self.len > 0 {
+            line.clear();
+
+            let size = reader.read_line(&mut line)?;
+
+            if size == 0 {
+                self.len = 0;
+                break;
+            }
+
+            self.offset += size as u64;
+            self.len -= 1;
+
+            let mut fields = line.trim_end_matches('\n').splitn(3, ' ');
+
+            match (fields.next(), fields.next(), fields.next()) {
+                (Some(priority), Some(depth), Some(link)) if !link.is_empty() => {
+                    links.push(SpilledLink {
+                        link: link.into(),
+                        depth: depth.parse().unwrap_or_default(),
+                        priority: priority.parse().unwrap_or_default(),
+                    })
+                }
+                _ => (),
+            }
+        }
+
+        if self.len == 0 {
+            self.writer.get_ref().set_len(0)?;
+            self.offset = 0;
+        }
+
+        Ok(links)
+    }
+
+    /// Get the count of the links not read.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// All the links appended were read.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+}
+
+impl Drop for Segment {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(&self.path);
+    }
+}
+
+/// The segments of the links spilled from the frontier.
+#[derive(Debug)]
+pub struct FrontierSegments {
+    /// The directory of the segment files.
+    dir: PathBuf,
+    /// The max links queued kept in memory.
+    window: usize,
+    /// The segments of the links spilled per depth for breadth-first.
+    depths: BTreeMap<usize, Segment>,
+    /// The segments of the links spilled read back last first for depth-first and priority.
+    stack: Vec<Segment>,
+}
+
+impl FrontierSegments {
+    /// Establish the segments of the frontier creating their directory.
+    pub fn new(spill: &FrontierSpill) -> std::io::Result<Self> {
+        std::fs::create_dir_all(&spill.dir)?;
+
+        Ok(Self {
+            dir: spill.dir.clone(),
+            window: spill.window.max(1),
+            depths: Default::default(),
+            stack: Default::default(),
+        })
+    }
+
+    /// Get the max links queued kept in memory.
+    pub fn get_window(&self) -> usize {
+        self.window
+    }
+
+    /// Get the count of the links spilled not read back.
+    pub fn len(&self) -> usize {
+        self.depths
+            .values()
+            .chain(self.stack.iter())
+            .map(|segment| segment.len())
+            .sum()
+    }
+
+    /// No links are spilled.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Get the shallowest depth of the links spilled for breadth-first.
+    pub fn get_min_depth(&self) -> Option<usize> {
+        self.depths.keys().next().copied()
+    }
+
+    /// Append the links to the segment of their depth for breadth-first.
+    pub fn push_depth(&mut self, depth: usize, links: &[SpilledLink]) -> std::io::Result<()> {
+        if !self.depths.contains_key(&depth) {
+            self.depths.insert(depth, Segment::new(&self.dir)?);
+        }
+
+        match self.depths.get_mut(&depth) {
+            Some(segment) => segment.append(links),
+            _ => Ok(()),
+        }
+    }
+
+    /// Read back a window of the links spilled of the shallowest depth for breadth-first.
+    pub fn pop_depth(&mut self) -> std::io::Result<Vec<SpilledLink>> {
+        let depth = match self.get_min_depth() {
+            Some(depth) => depth,
+            _ => return Ok(Default::default()),
+        };
+        let links = match self.depths.get_mut(&depth) {
+            Some(segment) => segment.read(self.window)?,
+            _ => Default::default(),
+        };
+
+        if self
+            .depths
+            .get(&depth)
+            .map_or(true, |segment| segment.is_empty())
+        {
+            self.depths.remove(&depth);
+        }
+
+        Ok(links)
+    }
+
+    /// Spill the links to a new segment read back before the segments spilled earlier.
+    pub fn push_segment(&mut self, links: &[SpilledLink]) -> std::io::Result<()> {
+        let mut segment = Segment::new(&self.dir)?;
+
+        segment.append(links)?;
+        self.stack.push(segment);
+
+        Ok(())
+    }
+
+    /// Read back the links of the last segment spilled for depth-first and priority.
+    pub fn pop_segment(&mut self) -> std::io::Result<Vec<SpilledLink>> {
+        match self.stack.pop() {
+            Some(mut segment) => segment.read(usize::MAX),
+            _ => Ok(Default::default()),
+        }
+    }
+
+    /// Read back all the links spilled removing their segments.
+    pub fn drain(&mut self) -> Vec<SpilledLink> {
+        let mut links = Vec::new();
+
+        for (_, mut segment) in std::mem::take(&mut self.depths) {
+            links.extend(segment.read(usize::MAX).unwrap_or_default());
+        }
+
+        for mut segment in std::mem::take(&mut self.stack) {
+            links.extend(segment.read(usize::MAX).unwrap_or_default());
+        }
+
+        links
+    }
+}
+
+/// The links queued to crawl shared by the crawls of several websites or processes.
+pub trait SharedFrontier: std::fmt::Debug + Send + Sync {
+    /// Queue the links. The links queued already keep their priority.
+    fn push(&self, links: &[SpilledLink]);
+    /// Pop up to the max links with the highest priority. A link queued is popped by a single crawl.
+    fn pop(&self, max: usize) -> Vec<SpilledLink>;
+    /// Get the count of the links queued.
+    fn len(&self) -> usize;
+    /// Remove all the links queued.
+    fn clear(&self);
+    /// No links are queued.
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// A link queued by its priority and the order queued to pop the links queued first on ties.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
+struct SharedLink(u32, std::cmp::Reverse<usize>, usize, CaseInsensitiveString);
+
+/// The links queued by their priority.
+#[derive(Debug, Default)]
+struct SharedQueue {
+    /// The links queued by their priority.
+    heap: BinaryHeap<SharedLink>,
+    /// The links queued to skip the links queued already.
+    queued: HashSet<CaseInsensitiveString>,
+    /// The count of the links queued.
+    count: usize,
+}
+
+/// The links queued kept in memory shared by the crawls of a process.
+#[derive(Debug, Default)]
+pub struct MemoryFrontier {
+    /// The links queued.
+    queue: Mutex<SharedQueue>,
+}
+
+impl MemoryFrontier {
+    /// Establish a new empty frontier in memory.
+    pub fn new() -> Self {
+        Default::default()
+    }
+}
+
+impl SharedFrontier for MemoryFrontier {
+    fn push(&self, links: &[SpilledLink]) {
+        match self.queue.lock() {
+            Ok(mut queue) => {
+                for link in links {
+                    if queue.queued.insert(link.link.clone()) {
+                        queue.count += 1;
+
+                        let order = std::cmp::Reverse(queue.count);
+
+                        queue.heap.push(SharedLink(
+                            link.priority,
+                            order,
+                            link.depth,
+                            link.link.clone(),
+                        ));
+                    }
+                }
+            }
+            _ => (),
+        }
+    }
+
+    fn pop(&self, max: usize) -> Vec<SpilledLink> {
+        match self.queue.lock() {
+            Ok(mut queue) => {
+                let mut links = Vec::new();
+
+                while links.len() < max {
+                    match queue.heap.pop() {
+                        Some(SharedLink(priority, _, depth, link)) => {
+                            queue.queued.remove(&link);
+                            links.push(SpilledLink {
+                                link,
+                                depth,
+                                priority,
+                            });
+                        }
+                        _ => break,
+                    }
+                }
+
+                links
+            }
+            _ => Default::default(),
+        }
+    }
+
+    fn len(&self) -> usize {
+        match self.queue.lock() {
+            Ok(queue) => queue.heap.len(),
+            _ => 0,
+        }
+    }
+
+    fn clear(&self) {
+        match self.queue.lock() {
+            Ok(mut queue) => *queue = Default::default(),
+            _ => (),
+        }
+    }
+}
@@ -0,0 +1,246 @@
+#[cfg(feature = "link_graph")]
+use crate::utils::escape_xml;
+#[cfg(feature = "link_graph")]
+use crate::CaseInsensitiveString;
+#[cfg(feature = "link_graph")]
+use hashbrown::{HashMap, HashSet};
+use std::io::Write;
+use std::path::Path;
+use std::sync::Mutex;
+
+/// A link from a page to the url of an anchor.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Hash)]
+pub struct LinkEdge {
+    /// The url of the page of the anchor.
+    pub source: String,
+    /// The absolute url of the anchor.
+    pub target: String,
+    /// The text of the anchor with the whitespace collapsed.
+    pub anchor_text: String,
+    /// The rel of the anchor.
+    pub rel: String,
+}
+
+/// The edges of the pages crawled shared across the tasks of the crawl.
+#[derive(Debug, Default)]
+pub struct LinkGraph {
+    /// The edges recorded in the order of the pages crawled.
+    edges: Mutex<Vec<LinkEdge>>,
+    #[cfg(feature = "link_graph")]
+    /// The adjacency of the urls linked.
+    adjacency: Mutex<Adjacency>,
+}
+
+/// The urls linked from and to each url.
+#[cfg(feature = "link_graph")]
+#[derive(Debug, Default)]
+struct Adjacency {
+    /// The urls linked from the url.
+    outlinks: HashMap<CaseInsensitiveString, HashSet<CaseInsensitiveString>>,
+    /// The urls linking to the url.
+    inlinks: HashMap<CaseInsensitiveString, HashSet<CaseInsensitiveString>>,
+}
+
+impl LinkGraph {
+    /// Establish a new empty link graph.
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Record the edges of a page.
+    pub fn extend(&self, edges: Vec<LinkEdge>) {
+        #[cfg(feature = "link_graph")]
+        match self.adjacency.lock() {
+            Ok(mut adjacency) => {
+                for edge in edges.iter() {
+                    let source = CaseInsensitiveString::from(edge.source.as_str());
+                    let target = CaseInsensitiveString::from(edge.target.as_str());
+
+                    adjacency
+                        .outlinks
+                        .entry(source.clone())
+                        .or_default()
+                        .insert(target.clone());
+                    adjacency.inlinks.entry(target).or_default().insert(source);
+                }
+            }
+            _ => (),
+        }
+
+        match self.edges.lock() {
+            Ok(mut e) => e.extend(edges),
+            _ => (),
+        }
+    }
+
+    /// Get the urls linked from the url.
+    #[cfg(feature = "link_graph")]
+    pub fn get_outlinks(&self, url: &str) -> HashSet<CaseInsensitiveString> {
+        match self.adjacency.lock() {
+            Ok(adjacency) => adjacency
+                .outlinks
+                .get(&CaseInsensitiveString::from(url))
+                .cloned()
+                .unwrap_or_default(),
+            _ => Default::default(),
+        }
+    }
+
+    /// Get the urls linking to the url.
+    #[cfg(feature = "link_graph")]
+    pub fn get_inlinks(&self, url: &str) -> HashSet<CaseInsensitiveString> {
+        match self.adjacency.lock() {
+            Ok(adjacency) => adjacency
+                .inlinks
+                .get(&CaseInsensitiveString::from(url))
+                .cloned()
+                .unwrap_or_default(),
+            _ => Default::default(),
+        }
+    }
+
+    /// Get the urls linked with their outlinks sorted to export the graph.
+    #[cfg(feature = "link_graph")]
+    fn get_adjacency(&self) -> Vec<(CaseInsensitiveString, Vec<CaseInsensitiveString>)> {
+        let mut adjacency: Vec<(CaseInsensitiveString, Vec<CaseInsensitiveString>)> =
+            match self.adjacency.lock() {
+                Ok(adjacency) => {
+                    let mut urls: HashSet<&CaseInsensitiveString> =
+                        adjacency.outlinks.keys().collect();
+                    urls.extend(adjacency.inlinks.keys());
+
+                    urls.into_iter()
+                        .map(|url| {
+                            let mut outlinks: Vec<CaseInsensitiveString> = adjacency
+                                .outlinks
+                                .get(url)
+                                .map(|outlinks| outlinks.iter().cloned().collect())
+                                .unwrap_or_default();
+                            outlinks.sort_by(|a, b| a.inner().cmp(b.inner()));
+
+                            (url.clone(), outlinks)
+                        })
+                        .collect()
+                }
+                _ => Default::default(),
+            };
+        adjacency.sort_by(|a, b| a.0.inner().cmp(b.0.inner()));
+
+        adjacency
+    }
+
+    /// Get the DOT digraph of the urls linked.
+    #[cfg(feature = "link_graph")]
+    pub fn to_dot(&self) -> String {
+        let mut dot = String::from("digraph {\n");
+
+        for (url, outlinks) in self.get_adjacency() {
+            dot.push_str(&format!("  \"{}\";\n", escape_dot(url.inner())));
+
+            for outlink in outlinks {
+                dot.push_str(&format!(
+                    "  \"{}\" -> \"{}\";\n",
+                    escape_dot(url.inner()),
+                    escape_dot(outlink.inner())
+                ));
+            }
+        }
+        dot.push_str("}\n");
+
+        dot
+    }
+
+    /// Get the GraphML document of the urls linked with the url as the id of the nodes.
+    #[cfg(feature = "link_graph")]
+    pub fn to_graphml(&self) -> String {
+        let adjacency = self.get_adjacency();
+        let mut graphml = String::from(concat!(
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n",
+            "<graphml xmlns=\"http://graphml.graphdrawing.org/xmlns\">\n",
+            "  <graph id=\"links\" edgedefault=\"directed\">\n"
+        ));
+
+        for (url, _) in adjacency.iter() {
+            graphml.push_str(&format!("    <node id=\"{}\"/>\n", escape_xml(url.inner())));
+        }
+        for (url, outlinks) in adjacency.iter() {
+            for outlink in outlinks {
+                graphml.push_str(&format!(
+                    "    <edge source=\"{}\" target=\"{}\"/>\n",
+                    escape_xml(url.inner()),
+                    escape_xml(outlink.inner())
+                ));
+            }
+        }
+        graphml.push_str("  </graph>\n</graphml>\n");
+
+        graphml
+    }
+
+    /// Get the edges recorded.
+    pub fn get_edges(&self) -> Vec<LinkEdge> {
+        match self.edges.lock() {
+            Ok(edges) => edges.clone(),
+            _ => Default::default(),
+        }
+    }
+
+    /// Remove all the edges recorded.
+    pub fn clear(&self) {
+        #[cfg(feature = "link_graph")]
+        match self.adjacency.lock() {
+            Ok(mut adjacency) => *adjacency = Default::default(),
+            _ => (),
+        }
+
+        match self.edges.lock() {
+            Ok(mut edges) => edges.clear(),
+            _ => (),
+        }
+    }
+
+    /// Write the edges recorded as CSV rows with a header.
+    pub fn write_csv<W: Write>(&self, mut writer: W) -> std::io::Result<()> {
+        writer.write_all(b"source_url,target_url,anchor_text,rel\n")?;
+
+        for edge in self.get_edges() {
+            writer.write_all(
+                format!(
+                    "{},{},{},{}\n",
+                    escape_csv(&edge.source),
+                    escape_csv(&edge.target),
+                    escape_csv(&edge.anchor_text),
+                    escape_csv(&edge.rel)
+                )
+                .as_bytes(),
+            )?;
+        }
+
+        writer.flush()
+    }
+
+    /// Write the edges recorded to the CSV file at the path creating its directory when missing.
+    pub fn save_csv<P: AsRef<Path>>(&self, path: P) -> std::io::Result<()> {
+        match path.as_ref().parent() {
+            Some(dir) if !dir.as_os_str().is_empty() => std::fs::create_dir_all(dir)?,
+            _ => (),
+        }
+
+        self.write_csv(std::io::BufWriter::new(std::fs::File::create(path)?))
+    }
+}
+
+/// Quote the CSV field when it contains a comma, a quote, or a line break.
+pub(crate) fn escape_csv(field: &str) -> String {
+    if field.contains(|c| c == ',' || c == '"' || c == '\n' || c == '\r') {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.into()
+    }
+}
+
+/// Escape the url for a quoted DOT id.
+#[cfg(feature = "link_graph")]
+fn escape_dot(url: &str) -> String {
+    url.replace('\\', "\\\\").replace('"', "\\\"")
+}
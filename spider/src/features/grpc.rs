@@ -0,0 +1,166 @@
+/// The messages and the server of the `Crawler` service generated from `proto/spider.proto`.
+pub mod proto {
+    tonic::include_proto!("spider");
+}
+
+use crate::features::daemon::{CrawlEntry, CrawlRegistry};
+use crate::utils::log;
+use crate::website::Website;
+use proto::crawler_server::{Crawler, CrawlerServer};
+use proto::{
+    CancelRequest, CancelResponse, GetStatusRequest, GetStatusResponse, StartCrawlRequest,
+    StartCrawlResponse, StreamPagesRequest,
+};
+use std::net::SocketAddr;
+use std::pin::Pin;
+use std::sync::Arc;
+use tokio::sync::{broadcast, mpsc};
+use tokio_stream::wrappers::ReceiverStream;
+use tonic::{Request, Response, Status};
+
+/// The gRPC service starting and controlling the crawls of the daemon.
+#[derive(Default)]
+pub struct CrawlService {
+    /// The crawls started by their id.
+    crawls: CrawlRegistry,
+}
+
+impl CrawlService {
+    /// Establish the service without crawls.
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Set the pages buffered per crawl for the streams slower than the crawl. The streams lagging
+    /// behind skip the pages dropped.
+    pub fn with_capacity(&mut self, capacity: usize) -> &mut Self {
+        self.crawls.capacity = capacity.max(1);
+        self
+    }
+
+    /// Get the server of the service to add to a `tonic` router.
+    pub fn into_server(self) -> CrawlerServer<Self> {
+        CrawlerServer::new(self)
+    }
+
+    /// Get the crawl of the id.
+    fn get_crawl(&self, id: &str) -> Result<Arc<CrawlEntry>, Status> {
+        self.crawls
+            .get(id)
+            .ok_or_else(|| Status::not_found(format!("crawl {} not found", id)))
+    }
+}
+
+/// Serve the gRPC service on the address until the server fails.
+pub async fn serve(addr: SocketAddr) -> Result<(), tonic::transport::Error> {
+    log("gRPC server listening", addr.to_string());
+
+    tonic::transport::Server::builder()
+        .add_service(CrawlService::new().into_server())
+        .serve(addr)
+        .await
+}
+
+#[tonic::async_trait]
+impl Crawler for CrawlService {
+    type StreamPagesStream =
+        Pin<Box<dyn tokio_stream::Stream<Item = Result<proto::Page, Status>> + Send>>;
+
+    async fn start_crawl(
+        &self,
+        request: Request<StartCrawlRequest>,
+    ) -> Result<Response<StartCrawlResponse>, Status> {
+        let request = request.into_inner();
+
+        if url::Url::parse(&request.url).is_err() {
+            return Err(Status::invalid_argument(format!(
+                "invalid url {}",
+                request.url
+            )));
+        }
+
+        let mut website: Website = Website::new(&request.url);
+
+        website
+            .with_limit(request.limit as usize)
+            .with_depth(request.depth as usize)
+            .with_delay(request.delay)
+            .with_subdomains(request.subdomains)
+            .with_respect_robots_txt(request.respect_robots_txt);
+
+        if !request.user_agent.is_empty() {
+            website.with_user_agent(Some(&request.user_agent));
+        }
+
+        match self.crawls.start(website) {
+            Some(id) => Ok(Response::new(StartCrawlResponse { id })),
+            _ => Err(Status::internal("the crawl could not be started")),
+        }
+    }
+
+    async fn get_status(
+        &self,
+        request: Request<GetStatusRequest>,
+    ) -> Result<Response<GetStatusResponse>, Status> {
+        let id = request.into_inner().id;
+        let crawl = self.get_crawl(&id)?;
+
+        Ok(Response::new(GetStatusResponse {
+            id,
+            url: crawl.url.clone(),
+            status: crawl.get_status(),
+            finished: crawl.is_finished(),
+            cancelled: crawl.is_cancelled(),
+            pages: crawl.get_pages(),
+            failures: crawl.get_failures(),
+        }))
+    }
+
+    async fn stream_pages(
+        &self,
+        request: Request<StreamPagesRequest>,
+    ) -> Result<Response<Self::StreamPagesStream>, Status> {
+        let request = request.into_inner();
+        let crawl = self.get_crawl(&request.id)?;
+        let mut pages = crawl.subscribe();
+        let (sender, receiver) = mpsc::channel(self.crawls.capacity);
+
+        tokio::spawn(async move {
+            loop {
+                match pages.recv().await {
+                    Ok(page) => {
+                        let message = proto::Page {
+                            url: page.get_url().to_string(),
+                            status_code: page.status_code.as_u16().into(),
+                            failed: page.is_failed(),
+                            body: match page.get_bytes() {
+                                Some(bytes) if request.body => bytes.to_vec(),
+                                _ => Default::default(),
+                            },
+                        };
+
+                        // the stream was dropped by the client
+                        if sender.send(Ok(message)).await.is_err() {
+                            break;
+                        }
+                    }
+                    Err(broadcast::error::RecvError::Lagged(_)) => (),
+                    _ => break,
+                }
+            }
+        });
+
+        Ok(Response::new(Box::pin(ReceiverStream::new(receiver))))
+    }
+
+    async fn cancel(
+        &self,
+        request: Request<CancelRequest>,
+    ) -> Result<Response<CancelResponse>, Status> {
+        let crawl = self.get_crawl(&request.into_inner().id)?;
+
+        Ok(Response::new(CancelResponse {
+            cancelled: crawl.cancel(),
+        }))
+    }
+}
@@ -0,0 +1,257 @@
+use crate::features::middleware::CrawlMiddleware;
+use crate::utils::{escape_json, get_iso_date, PageResponse};
+use hashbrown::HashMap;
+use reqwest::header::{HeaderMap, CONTENT_TYPE};
+use std::path::Path;
+use std::sync::Mutex;
+use std::time::{Duration, Instant, SystemTime};
+
+/// A request of the crawl with its response.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HarEntry {
+    /// The time the request started.
+    pub started: SystemTime,
+    /// The duration from the start of the request to the end of the response.
+    pub time: Duration,
+    /// The url requested.
+    pub url: String,
+    /// The headers added to the request on top of the headers of the client.
+    pub request_headers: Vec<(String, String)>,
+    /// The status code of the response. 0 when the request failed without a response.
+    pub status_code: u16,
+    /// The HTTP version of the response.
+    pub http_version: String,
+    /// The headers of the response.
+    pub response_headers: Vec<(String, String)>,
+    /// The size of the body of the response.
+    pub size: usize,
+    /// The final url of the request after the redirects.
+    pub redirect_url: String,
+    /// The error of the request failed without a response.
+    pub error: Option<String>,
+}
+
+/// The middleware recording the requests and the responses of the crawl to export as HAR.
+#[derive(Debug, Default)]
+pub struct HarRecorder {
+    /// The requests in flight per url with their start and headers.
+    pending: Mutex<HashMap<String, (SystemTime, Instant, Vec<(String, String)>)>>,
+    /// The requests completed.
+    entries: Mutex<Vec<HarEntry>>,
+}
+
+impl HarRecorder {
+    /// Establish a new HAR recorder.
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Get the requests recorded in the order of their responses.
+    pub fn get_entries(&self) -> Vec<HarEntry> {
+        match self.entries.lock() {
+            Ok(entries) => entries.clone(),
+            _ => Default::default(),
+        }
+    }
+
+    /// Remove all the requests recorded.
+    pub fn clear(&self) {
+        match self.entries.lock() {
+            Ok(mut entries) => entries.clear(),
+            _ => (),
+        }
+    }
+
+    /// Get the HAR 1.2 json of the requests recorded.
+    pub fn to_json(&self) -> String {
+        let entries: Vec<String> = self.get_entries().iter().map(get_entry_json).collect();
+
+        format!(
+            r#"{{"log":{{"version":"1.2","creator":{{"name":"spider","version":"{}"}},"pages":[],"entries":[{}]}}}}"#,
+            env!("CARGO_PKG_VERSION"),
+            entries.join(",")
+        )
+    }
+
+    /// Write the HAR 1.2 file of the requests recorded creating its directory when missing.
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> std::io::Result<()> {
+        match path.as_ref().parent() {
+            Some(dir) if !dir.as_os_str().is_empty() => std::fs::create_dir_all(dir)?,
+            _ => (),
+        }
+
+        std::fs::write(path, self.to_json())
+    }
+}
+
+impl CrawlMiddleware for HarRecorder {
+    fn before_request(&self, url: &str, headers: &mut HeaderMap) -> Option<PageResponse> {
+        match self.pending.lock() {
+            Ok(mut pending) => {
+                pending.insert(
+                    url.into(),
+                    (SystemTime::now(), Instant::now(), get_headers(headers)),
+                );
+            }
+            _ => (),
+        }
+
+        None
+    }
+
+    fn after_response(&self, url: &str, response: &mut PageResponse) {
+        let request = match self.pending.lock() {
+            Ok(mut pending) => pending.remove(url),
+            _ => None,
+        };
+
+        // the responses short-circuited by the middlewares before are not requested
+        match request {
+            Some((started, start, request_headers)) => {
+                let entry = HarEntry {
+                    started,
+                    time: start.elapsed(),
+                    url: url.into(),
+                    request_headers,
+                    status_code: match response.error_for_status {
+                        Some(Err(_)) => 0,
+                        _ => response.status_code.as_u16(),
+                    },
+                    http_version: match response.http_version {
+                        Some(http_version) => format!("{:?}", http_version),
+                        _ => Default::default(),
+                    },
+                    response_headers: match response.headers.as_ref() {
+                        Some(headers) => get_headers(headers),
+                        _ => Default::default(),
+                    },
+                    size: response.content.as_ref().map_or(0, |content| content.len()),
+                    redirect_url: response.final_url.clone().unwrap_or_default(),
+                    error: match &response.error_for_status {
+                        Some(Err(e)) => Some(e.to_string()),
+                        _ => None,
+                    },
+                };
+
+                match self.entries.lock() {
+                    Ok(mut entries) => entries.push(entry),
+                    _ => (),
+                }
+            }
+            _ => (),
+        }
+    }
+}
+
+/// Get the name and value pairs of the headers.
+fn get_headers(headers: &HeaderMap) -> Vec<(String, String)> {
+    headers
+        .iter()
+        .map(|(name, value)| {
+            (
+                name.as_str().into(),
+                String::from_utf8_lossy(value.as_bytes()).into(),
+            )
+        })
+        .collect()
+}
+
+/// Get the json of the HAR entry.
+fn get_entry_json(entry: &HarEntry) -> String {
+    let time = entry.time.as_secs_f64() * 1000.0;
+    let query: Vec<(String, String)> = match url::Url::parse(&entry.url) {
+        Ok(url) => url.query_pairs().into_owned().collect(),
+        _ => Default::default(),
+    };
+    let mime_type = entry
+        .response_headers
+        .iter()
+        .find(|(name, _)| name == CONTENT_TYPE.as_str())
+        .map_or("", |(_, value)| value.as_str());
+    let error = match &entry.error {
+        Some(error) => format!(r#","_error":"{}""#, escape_json(error)),
+        _ => Default::default(),
+    };
+
+    format!(
+        concat!(
+            r#"{{"startedDateTime":"{}","time":{:.3},"#,
+            r#""request":{{"method":"GET","url":"{}","httpVersion":"{}","cookies":[],"headers":{},"queryString":{},"headersSize":-1,"bodySize":0}},"#,
+            r#""response":{{"status":{},"statusText":"{}","httpVersion":"{}","cookies":[],"headers":{},"content":{{"size":{},"mimeType":"{}"}},"redirectURL":"{}","headersSize":-1,"bodySize":{}}},"#,
+            r#""cache":{{}},"timings":{{"send":0,"wait":{:.3},"receive":0}}{}}}"#
+        ),
+        get_iso_date(entry.started),
+        time,
+        escape_json(&entry.url),
+        escape_json(&entry.http_version),
+        get_pairs_json(&entry.request_headers),
+        get_pairs_json(&query),
+        entry.status_code,
+        reqwest::StatusCode::from_u16(entry.status_code)
+            .ok()
+            .and_then(|status_code| status_code.canonical_reason())
+            .unwrap_or_default(),
+        escape_json(&entry.http_version),
+        get_pairs_json(&entry.response_headers),
+        entry.size,
+        escape_json(mime_type),
+        escape_json(&entry.redirect_url),
+        entry.size,
+        time,
+        error
+    )
+}
+
+/// Get the json array of the name and value pairs.
+fn get_pairs_json(pairs: &[(String, String)]) -> String {
+    let pairs: Vec<String> = pairs
+        .iter()
+        .map(|(name, value)| {
+            format!(
+                r#"{{"name":"{}","value":"{}"}}"#,
+                escape_json(name),
+                escape_json(value)
+            )
+        })
+        .collect();
+
+    format!("[{}]", pairs.join(","))
+}
+
+#[test]
+fn test_har_recorder() {
+    use reqwest::header::HeaderValue;
+
+    let recorder = HarRecorder::new();
+    let url = "https://choosealicense.com/licenses/?q=\"mit\"";
+    let mut headers = HeaderMap::new();
+    headers.insert("content-type", HeaderValue::from_static("text/html"));
+
+    assert!(recorder
+        .before_request(url, &mut HeaderMap::new())
+        .is_none());
+    recorder.after_response(
+        url,
+        &mut crate::utils::PageResponse {
+            content: Some(bytes::Bytes::from_static(b"<html></html>")),
+            status_code: reqwest::StatusCode::OK,
+            headers: Some(headers),
+            http_version: Some(reqwest::Version::HTTP_11),
+            ..Default::default()
+        },
+    );
+    // the responses short-circuited are not recorded
+    recorder.after_response("https://choosealicense.com/", &mut Default::default());
+
+    let entries = recorder.get_entries();
+    assert_eq!(entries.len(), 1);
+    assert_eq!(entries[0].status_code, 200);
+    assert_eq!(entries[0].size, 13);
+
+    let har = recorder.to_json();
+    assert!(har.starts_with(r#"{"log":{"version":"1.2","creator":{"name":"spider""#));
+    assert!(har.contains(r#""url":"https://choosealicense.com/licenses/?q=\"mit\"""#));
+    assert!(har.contains(r#""queryString":[{"name":"q","value":"\"mit\""}]"#));
+    assert!(har.contains(r#""content":{"size":13,"mimeType":"text/html"}"#));
+    assert!(har.contains(r#""status":200,"statusText":"OK","httpVersion":"HTTP/1.1""#));
+}
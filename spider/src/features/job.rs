@@ -0,0 +1,186 @@
+use crate::features::stats::CrawlStats;
+use crate::utils::log;
+use crate::website::{CrawlControl, CrawlStatus, CronType, Website};
+use chrono::{DateTime, Utc};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::{mpsc, Notify};
+
+/// The policy of the runs due while a run is running.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum OverlapPolicy {
+    #[default]
+    /// Skip the runs due while a run is running and wait for the next run scheduled after it ends.
+    Skip,
+    /// Queue the runs due while a run is running to start them one after the other once it ends.
+    Queue,
+}
+
+/// The result of a run of a job.
+#[derive(Debug, Clone)]
+pub struct CrawlRun {
+    /// The count of the run starting from 1.
+    pub run: u64,
+    /// The time the run was scheduled at.
+    pub scheduled: DateTime<Utc>,
+    /// The time the run started at.
+    pub started: DateTime<Utc>,
+    /// The duration of the run.
+    pub duration: Duration,
+    /// The status of the crawl at the end of the run.
+    pub status: CrawlStatus,
+    /// The links visited by the run.
+    pub links: usize,
+    /// The statistics of the requests of the run.
+    pub stats: CrawlStats,
+}
+
+/// The job crawling a website on the schedule of its cron.
+pub struct CrawlJob {
+    /// The website crawled by the runs.
+    website: Website,
+    /// The schedule of the runs.
+    schedule: cron::Schedule,
+    /// The policy of the runs due while a run is running.
+    overlap: OverlapPolicy,
+    /// The max runs of the job. Defaults to 0 for no limit.
+    max_runs: u64,
+}
+
+impl CrawlJob {
+    /// Establish the job crawling the website on the schedule of its cron set with `with_cron` like
+    /// `0 0 * * * *` with the cron type of the website.
+    pub fn new(website: Website) -> Result<Self, cron::error::Error> {
+        Ok(Self {
+            schedule: website.cron_str.parse()?,
+            website,
+            overlap: Default::default(),
+            max_runs: 0,
+        })
+    }
+
+    /// Set the policy of the runs due while a run is running.
+    pub fn with_overlap(&mut self, overlap: OverlapPolicy) -> &mut Self {
+        self.overlap = overlap;
+        self
+    }
+
+    /// Set the max runs of the job ending the job after them. Use 0 for no limit.
+    pub fn with_max_runs(&mut self, max_runs: u64) -> &mut Self {
+        self.max_runs = max_runs;
+        self
+    }
+
+    /// Start the job in a task of the runtime sending the result of each run to the handle.
+    pub fn start(self) -> CrawlJobHandle {
+        let (sender, receiver) = mpsc::unbounded_channel();
+        let stopped = Arc::new(AtomicBool::new(false));
+        let stop = Arc::new(Notify::new());
+        let control = self.website.get_control();
+        let task = tokio::spawn(self.run(sender, stopped.clone(), stop.clone()));
+
+        CrawlJobHandle {
+            receiver,
+            stopped,
+            stop,
+            control,
+            task,
+        }
+    }
+
+    /// Run the crawls on the schedule until the max runs or the job is stopped.
+    async fn run(
+        mut self,
+        sender: mpsc::UnboundedSender<CrawlRun>,
+        stopped: Arc<AtomicBool>,
+        stop: Arc<Notify>,
+    ) -> Website {
+        let mut run = 0;
+        let mut scheduled = Utc::now();
+
+        while self.max_runs == 0 || run < self.max_runs {
+            // the queued runs are the runs scheduled after the previous run
+            let next = match self.overlap {
+                OverlapPolicy::Skip => self.schedule.after(&Utc::now()).next(),
+                OverlapPolicy::Queue => self.schedule.after(&scheduled).next(),
+            };
+            let next = match next {
+                Some(next) => next,
+                _ => break,
+            };
+
+            tokio::select! {
+                _ = tokio::time::sleep((next - Utc::now()).to_std().unwrap_or_default()) => (),
+                _ = stop.notified() => (),
+            }
+
+            if stopped.load(Ordering::Relaxed) {
+                break;
+            }
+
+            run += 1;
+            scheduled = next;
+
+            let started = Utc::now();
+            let start = Instant::now();
+
+            log("job run", self.website.get_domain().inner());
+
+            match self.website.cron_type {
+                CronType::Crawl => self.website.crawl().await,
+                CronType::Scrape => self.website.scrape().await,
+            }
+
+            let _ = sender.send(CrawlRun {
+                run,
+                scheduled,
+                started,
+                duration: start.elapsed(),
+                status: self.website.get_status().clone(),
+                links: self.website.size(),
+                stats: self.website.get_stats(),
+            });
+
+            if stopped.load(Ordering::Relaxed) {
+                break;
+            }
+        }
+
+        self.website
+    }
+}
+
+/// The handle of a job started receiving the results of its runs.
+pub struct CrawlJobHandle {
+    /// The results of the runs.
+    receiver: mpsc::UnboundedReceiver<CrawlRun>,
+    /// The job is stopped.
+    stopped: Arc<AtomicBool>,
+    /// Wake the job waiting for the next run when stopped.
+    stop: Arc<Notify>,
+    /// The control of the crawls of the website shutting down the run in progress.
+    control: CrawlControl,
+    /// The task of the job returning the website once ended.
+    task: tokio::task::JoinHandle<Website>,
+}
+
+impl CrawlJobHandle {
+    /// Receive the result of the next run. None once the job ended.
+    pub async fn recv(&mut self) -> Option<CrawlRun> {
+        self.receiver.recv().await
+    }
+
+    /// Stop the job shutting down the run in progress and get the website back.
+    pub async fn stop(self) -> Option<Website> {
+        self.stopped.store(true, Ordering::Relaxed);
+        self.stop.notify_one();
+        self.control.shutdown();
+        self.task.await.ok()
+    }
+
+    /// Wait for the job to end after its max runs and get the website back.
+    pub async fn join(self) -> Option<Website> {
+        self.task.await.ok()
+    }
+}
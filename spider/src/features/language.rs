@@ -0,0 +1,143 @@
+use crate::packages::scraper::Html;
+use compact_str::CompactString;
+
+/// The max chars of the text of the page used to detect its language.
+const LANGUAGE_SCAN_SIZE: usize = 4096;
+
+/// Detect the language of the html from the text of its body falling back to the `lang` of the
+/// `<html>` tag.
+pub fn detect_language(html: &Html) -> Option<CompactString> {
+    let mut text = String::new();
+    let mut lang: Option<CompactString> = None;
+
+    for node in html.tree.root().descendants() {
+        match node.value().as_element() {
+            Some(element) if element.name() == "html" && lang.is_none() => {
+                lang = element
+                    .attr("lang")
+                    .and_then(|lang| lang.split(|c| c == '-' || c == '_').next())
+                    .map(|lang| lang.trim().to_ascii_lowercase())
+                    .filter(|lang| !lang.is_empty())
+                    .map(CompactString::from);
+            }
+            _ => (),
+        }
+
+        match node.value().as_text() {
+            Some(t)
+                if text.len() < LANGUAGE_SCAN_SIZE
+                    && node.ancestors().all(|ancestor| {
+                        ancestor.value().as_element().map_or(true, |element| {
+                            !matches!(
+                                element.name(),
+                                "head" | "script" | "style" | "noscript" | "template"
+                            )
+                        })
+                    }) =>
+            {
+                for word in t.split_whitespace() {
+                    text.push_str(word);
+                    text.push(' ');
+                }
+            }
+            _ => (),
+        }
+    }
+
+    match whatlang::detect(&text) {
+        Some(info) if info.is_reliable() => Some(get_iso_639_1(info.lang()).into()),
+        _ => lang,
+    }
+}
+
+/// The language detected is allowed by the languages like `en` or `en-US` matched by their primary
+/// subtag. The pages without a language detected are allowed.
+pub fn is_language_allowed(languages: &[CompactString], language: Option<&str>) -> bool {
+    match language {
+        Some(language) => languages.iter().any(|allowed| {
+            allowed
+                .split(|c| c == '-' || c == '_')
+                .next()
+                .map_or(false, |allowed| {
+                    allowed.trim().eq_ignore_ascii_case(language)
+                })
+        }),
+        _ => true,
+    }
+}
+
+/// Get the ISO 639-1 code of the language detected.
+fn get_iso_639_1(lang: whatlang::Lang) -> &'static str {
+    match lang.code() {
+        "epo" => "eo",
+        "eng" => "en",
+        "rus" => "ru",
+        "cmn" => "zh",
+        "spa" => "es",
+        "por" => "pt",
+        "ita" => "it",
+        "ben" => "bn",
+        "fra" => "fr",
+        "deu" => "de",
+        "ukr" => "uk",
+        "kat" => "ka",
+        "ara" => "ar",
+        "hin" => "hi",
+        "jpn" => "ja",
+        "heb" => "he",
+        "yid" => "yi",
+        "pol" => "pl",
+        "amh" => "am",
+        "jav" => "jv",
+        "kor" => "ko",
+        "nob" => "nb",
+        "dan" => "da",
+        "swe" => "sv",
+        "fin" => "fi",
+        "tur" => "tr",
+        "nld" => "nl",
+        "hun" => "hu",
+        "ces" => "cs",
+        "ell" => "el",
+        "bul" => "bg",
+        "bel" => "be",
+        "mar" => "mr",
+        "kan" => "kn",
+        "ron" => "ro",
+        "slv" => "sl",
+        "hrv" => "hr",
+        "srp" => "sr",
+        "mkd" => "mk",
+        "lit" => "lt",
+        "lav" => "lv",
+        "est" => "et",
+        "tam" => "ta",
+        "vie" => "vi",
+        "urd" => "ur",
+        "tha" => "th",
+        "guj" => "gu",
+        "uzb" => "uz",
+        "pan" => "pa",
+        "aze" => "az",
+        "ind" => "id",
+        "tel" => "te",
+        "pes" => "fa",
+        "mal" => "ml",
+        "ori" => "or",
+        "mya" => "my",
+        "nep" => "ne",
+        "sin" => "si",
+        "khm" => "km",
+        "tuk" => "tk",
+        "aka" => "ak",
+        "zul" => "zu",
+        "sna" => "sn",
+        "afr" => "af",
+        "lat" => "la",
+        "slk" => "sk",
+        "cat" => "ca",
+        "tgl" => "tl",
+        "hye" => "hy",
+        code => code,
+    }
+}
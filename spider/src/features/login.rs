@@ -0,0 +1,73 @@
+use crate::configuration::LoginForm;
+use crate::packages::scraper::{Html, Selector};
+use crate::utils::{get_body_bytes, log, send_request};
+use reqwest::Client;
+
+/// Get the names and the values of the hidden fields of the forms of the html like the CSRF token.
+pub fn get_hidden_fields(html: &str) -> Vec<(String, String)> {
+    let mut fields = Vec::new();
+
+    match Selector::parse(r#"form input[type="hidden"][name]"#) {
+        Ok(selector) => {
+            for element in Html::parse_document(html).select(&selector) {
+                let input = element.value();
+
+                match input.attr("name") {
+                    Some(name) => fields.push((
+                        name.to_string(),
+                        input.attr("value").unwrap_or_default().to_string(),
+                    )),
+                    _ => (),
+                }
+            }
+        }
+        _ => (),
+    }
+
+    fields
+}
+
+/// Post the login form with the client storing the session cookies. False when the login failed.
+pub(crate) async fn login(client: &Client, login: &LoginForm) -> bool {
+    // the login script is run on the browser of the crawl instead
+    #[cfg(feature = "chrome")]
+    if login.script.is_some() {
+        return true;
+    }
+
+    let mut fields = match &login.form_page {
+        Some(form_page) => match send_request(form_page, client, Default::default())
+            .await
+            .and_then(|res| res.error_for_status())
+        {
+            Ok(res) => get_hidden_fields(&String::from_utf8_lossy(
+                &get_body_bytes(res).await.unwrap_or_default(),
+            )),
+            Err(e) => {
+                log("login form page failed", e.to_string());
+                Default::default()
+            }
+        },
+        _ => Vec::new(),
+    };
+
+    fields.retain(|(name, _)| !login.fields.iter().any(|(field, _)| field == name));
+    fields.extend(login.fields.iter().cloned());
+
+    match client
+        .post(&login.url)
+        .form(&fields)
+        .send()
+        .await
+        .and_then(|res| res.error_for_status())
+    {
+        Ok(_) => {
+            log("login succeeded", &login.url);
+            true
+        }
+        Err(e) => {
+            log("login failed", e.to_string());
+            false
+        }
+    }
+}
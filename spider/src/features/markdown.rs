@@ -0,0 +1,402 @@
+use crate::packages::scraper::{Html, Node};
+use ego_tree::NodeRef;
+use url::Url;
+
+/// The elements skipped with their content.
+const SKIP_TAGS: [&str; 13] = [
+    "head", "script", "style", "noscript", "template", "iframe", "svg", "canvas", "form", "button",
+    "select", "textarea", "dialog",
+];
+
+/// The elements of the blocks of the markdown.
+const BLOCK_TAGS: [&str; 33] = [
+    "address",
+    "article",
+    "aside",
+    "blockquote",
+    "body",
+    "dd",
+    "details",
+    "div",
+    "dl",
+    "dt",
+    "fieldset",
+    "figcaption",
+    "figure",
+    "footer",
+    "h1",
+    "h2",
+    "h3",
+    "h4",
+    "h5",
+    "h6",
+    "header",
+    "hr",
+    "html",
+    "li",
+    "main",
+    "nav",
+    "ol",
+    "p",
+    "pre",
+    "section",
+    "summary",
+    "table",
+    "ul",
+];
+
+/// Convert the html to markdown with the links and the images resolved with the base url.
+pub fn html_to_markdown(html: &Html, base: &Url) -> String {
+    let mut blocks: Vec<String> = Vec::new();
+
+    write_blocks(html.tree.root(), base, &mut blocks);
+
+    blocks.join("\n\n")
+}
+
+/// Get the name of the element of the node.
+fn get_name(node: NodeRef<Node>) -> Option<&str> {
+    node.value().as_element().map(|element| element.name())
+}
+
+/// The node is an element of the blocks of the markdown.
+fn is_block(node: NodeRef<Node>) -> bool {
+    get_name(node).map_or(false, |name| BLOCK_TAGS.contains(&name))
+}
+
+/// Push the text with its whitespace collapsed to a single space.
+fn push_text(out: &mut String, text: &str) {
+    for c in text.chars() {
+        if c.is_whitespace() {
+            match out.chars().last() {
+                Some(last) if !last.is_whitespace() => out.push(' '),
+                _ => (),
+            }
+        } else {
+            out.push(c);
+        }
+    }
+}
+
+/// Push the inline markdown of the paragraph to the blocks when it is not empty.
+fn flush_inline(inline: &mut String, blocks: &mut Vec<String>) {
+    let paragraph = inline.trim();
+
+    if !paragraph.is_empty() {
+        blocks.push(paragraph.to_string());
+    }
+
+    inline.clear();
+}
+
+/// Write the blocks of the children of the node with their inline content grouped in paragraphs.
+fn write_blocks(node: NodeRef<Node>, base: &Url, blocks: &mut Vec<String>) {
+    let mut inline = String::new();
+
+    for child in node.children() {
+        match child.value() {
+            Node::Text(text) => push_text(&mut inline, text),
+            Node::Element(element) if SKIP_TAGS.contains(&element.name()) => (),
+            Node::Element(_) if is_block(child) => {
+                flush_inline(&mut inline, blocks);
+                write_block(child, base, blocks);
+            }
+            Node::Element(_) => write_inline(child, base, &mut inline),
+            _ => (),
+        }
+    }
+
+    flush_inline(&mut inline, blocks);
+}
+
+/// Write the block of the element.
+fn write_block(node: NodeRef<Node>, base: &Url, blocks: &mut Vec<String>) {
+    match get_name(node).unwrap_or_default() {
+        name @ ("h1" | "h2" | "h3" | "h4" | "h5" | "h6") => {
+            let mut heading = String::new();
+
+            write_children_inline(node, base, &mut heading);
+
+            let heading = heading.trim();
+
+            if !heading.is_empty() {
+                blocks.push(format!(
+                    "{} {}",
+                    "#".repeat(name[1..].parse().unwrap_or(1)),
+                    heading
+                ));
+            }
+        }
+        "hr" => blocks.push("---".into()),
+        "pre" => blocks.push(get_code_block(node)),
+        "ul" | "ol" => {
+            let list = get_list(node, base);
+
+            if !list.is_empty() {
+                blocks.push(list);
+            }
+        }
+        "blockquote" => {
+            let mut quote: Vec<String> = Vec::new();
+
+            write_blocks(node, base, &mut quote);
+
+            if !quote.is_empty() {
+                blocks.push(
+                    quote
+                        .join("\n\n")
+                        .lines()
+                        .map(|line| match line {
+                            "" => ">".to_string(),
+                            line => format!("> {}", line),
+                        })
+                        .collect::<Vec<_>>()
+                        .join("\n"),
+                );
+            }
+        }
+        "table" => {
+            let table = get_table(node, base);
+
+            if !table.is_empty() {
+                blocks.push(table);
+            }
+        }
+        _ => write_blocks(node, base, blocks),
+    }
+}
+
+/// Write the inline markdown of the children of the node.
+fn write_children_inline(node: NodeRef<Node>, base: &Url, out: &mut String) {
+    for child in node.children() {
+        match child.value() {
+            Node::Text(text) => push_text(out, text),
+            Node::Element(element) if SKIP_TAGS.contains(&element.name()) => (),
+            Node::Element(_) => {
+                // the blocks nested in the inline elements are separated by a space
+                if is_block(child) {
+                    push_text(out, " ");
+                }
+                write_inline(child, base, out);
+            }
+            _ => (),
+        }
+    }
+}
+
+/// Write the inline markdown of the element.
+fn write_inline(node: NodeRef<Node>, base: &Url, out: &mut String) {
+    let element = match node.value().as_element() {
+        Some(element) => element,
+        _ => return,
+    };
+
+    match element.name() {
+        "br" => out.push_str("  \n"),
+        "img" => match element.attr("src") {
+            Some(src) if !src.trim().is_empty() => out.push_str(&format!(
+                "![{}]({})",
+                element.attr("alt").unwrap_or_default().trim(),
+                get_url(base, src.trim())
+            )),
+            _ => (),
+        },
+        "a" => {
+            let mut text = String::new();
+
+            write_children_inline(node, base, &mut text);
+
+            match element.attr("href").map(|href| href.trim()) {
+                Some(href) if !href.is_empty() && !href.starts_with("javascript:") => {
+                    push_wrapped(out, &text, "[", &format!("]({})", get_url(base, href)))
+                }
+                _ => push_text(out, &text),
+            }
+        }
+        "code" | "kbd" | "samp" => {
+            let mut code = String::new();
+
+            push_text(&mut code, &get_text(node));
+
+            let code = code.trim();
+
+            if !code.is_empty() {
+                // the code with backticks is fenced with a double backtick and spaces
+                if code.contains('`') {
+                    out.push_str(&format!("`` {} ``", code));
+                } else {
+                    out.push_str(&format!("`{}`", code));
+                }
+            }
+        }
+        "strong" | "b" => write_wrapped(node, base, out, "**"),
+        "em" | "i" => write_wrapped(node, base, out, "*"),
+        "del" | "s" | "strike" => write_wrapped(node, base, out, "~~"),
+        _ => write_children_inline(node, base, out),
+    }
+}
+
+/// Write the inline markdown of the children of the element between the marker.
+fn write_wrapped(node: NodeRef<Node>, base: &Url, out: &mut String, marker: &str) {
+    let mut text = String::new();
+
+    write_children_inline(node, base, &mut text);
+    push_wrapped(out, &text, marker, marker);
+}
+
+/// Push the text between the open and the close markers keeping its surrounding whitespace outside
+/// of the markers.
+fn push_wrapped(out: &mut String, text: &str, open: &str, close: &str) {
+    let trimmed = text.trim();
+
+    if trimmed.is_empty() {
+        return push_text(out, text);
+    }
+
+    if text.starts_with(char::is_whitespace) {
+        push_text(out, " ");
+    }
+
+    out.push_str(open);
+    out.push_str(trimmed);
+    out.push_str(close);
+
+    if text.ends_with(char::is_whitespace) {
+        out.push(' ');
+    }
+}
+
+/// Get the raw text of the node and its descendants.
+fn get_text(node: NodeRef<Node>) -> String {
+    node.descendants()
+        .filter_map(|node| node.value().as_text())
+        .map(|text| &**text)
+        .collect()
+}
+
+/// Get the absolute url of the link with the base url.
+fn get_url(base: &Url, href: &str) -> String {
+    match base.join(href) {
+        Ok(url) => url.into(),
+        _ => href.into(),
+    }
+}
+
+/// Get the fenced code block of the `<pre>` with the language of its class like `language-rust`.
+fn get_code_block(node: NodeRef<Node>) -> String {
+    let text = get_text(node);
+    let code = text.trim_matches('\n').trim_end();
+    let language = node
+        .descendants()
+        .filter_map(|node| node.value().as_element())
+        .filter_map(|element| element.attr("class"))
+        .flat_map(|class| class.split_whitespace())
+        .find_map(|class| {
+            class
+                .strip_prefix("language-")
+                .or_else(|| class.strip_prefix("lang-"))
+        })
+        .unwrap_or_default();
+    let mut fence = String::from("```");
+
+    // the fence is longer than the backticks of the code
+    while code.contains(fence.as_str()) {
+        fence.push('`');
+    }
+
+    format!("{}{}\n{}\n{}", fence, language, code, fence)
+}
+
+/// Get the items of the `<ul>` or the `<ol>` with their nested blocks indented.
+fn get_list(node: NodeRef<Node>, base: &Url) -> String {
+    let ordered = get_name(node) == Some("ol");
+    let mut number: usize = node
+        .value()
+        .as_element()
+        .and_then(|element| element.attr("start"))
+        .and_then(|start| start.trim().parse().ok())
+        .unwrap_or(1);
+    let mut items: Vec<String> = Vec::new();
+
+    for child in node
+        .children()
+        .filter(|child| get_name(*child) == Some("li"))
+    {
+        let marker = if ordered {
+            let marker = format!("{}. ", number);
+            number += 1;
+            marker
+        } else {
+            "- ".to_string()
+        };
+        let mut blocks: Vec<String> = Vec::new();
+
+        write_blocks(child, base, &mut blocks);
+
+        let content = blocks.join("\n");
+        let mut lines = content.lines();
+        let mut item = format!("{}{}", marker, lines.next().unwrap_or_default());
+
+        for line in lines {
+            item.push('\n');
+
+            if !line.is_empty() {
+                item.push_str(&" ".repeat(marker.len()));
+                item.push_str(line);
+            }
+        }
+
+        items.push(item.trim_end().to_string());
+    }
+
+    items.join("\n")
+}
+
+/// Get the rows of the `<table>` as a pipe table with its first row as the header.
+fn get_table(node: NodeRef<Node>, base: &Url) -> String {
+    let mut rows: Vec<Vec<String>> = Vec::new();
+
+    for row in node
+        .descendants()
+        .filter(|row| get_name(*row) == Some("tr"))
+    {
+        // the rows of the nested tables are in the cells of their table
+        if row
+            .ancestors()
+            .take_while(|ancestor| ancestor.id() != node.id())
+            .any(|ancestor| get_name(ancestor) == Some("table"))
+        {
+            continue;
+        }
+
+        let cells: Vec<String> = row
+            .children()
+            .filter(|cell| matches!(get_name(*cell), Some("th" | "td")))
+            .map(|cell| {
+                let mut text = String::new();
+
+                write_children_inline(cell, base, &mut text);
+
+                text.replace("  \n", " ").trim().replace('|', "\\|")
+            })
+            .collect();
+
+        if !cells.is_empty() {
+            rows.push(cells);
+        }
+    }
+
+    let columns = rows.iter().map(|row| row.len()).max().unwrap_or_default();
+    let mut lines: Vec<String> = Vec::new();
+
+    for (i, row) in rows.iter_mut().enumerate() {
+        row.resize(columns, Default::default());
+        lines.push(format!("| {} |", row.join(" | ")));
+
+        if i == 0 {
+            lines.push(format!("|{}", " --- |".repeat(columns)));
+        }
+    }
+
+    lines.join("\n")
+}
@@ -0,0 +1,64 @@
+use crate::utils::PageResponse;
+use reqwest::header::HeaderMap;
+use reqwest::Request;
+use std::future::Future;
+use std::pin::Pin;
+
+/// The hooks around the requests and responses of a crawl.
+pub trait CrawlMiddleware: std::fmt::Debug + Send + Sync {
+    /// Run before the request of the url with the headers to send. Return a response to
+    /// short-circuit the request and skip the next middlewares.
+    fn before_request(&self, _url: &str, _headers: &mut HeaderMap) -> Option<PageResponse> {
+        None
+    }
+
+    /// Run right before the request built is sent with its method, url, headers, and body like to
+    /// sign it. The headers of `before_request` and of the client building it are set. Runs again
+    /// for every redirect followed with the url redirected to.
+    fn before_send(&self, _request: &mut Request) {}
+
+    /// Run after the response of the url before `after_response`. Return true to send the request
+    /// again once with the headers of the middlewares like after refreshing an expired token.
+    fn retry_request<'a>(
+        &'a self,
+        _url: &'a str,
+        _response: &'a PageResponse,
+    ) -> Pin<Box<dyn Future<Output = bool> + Send + 'a>> {
+        Box::pin(async { false })
+    }
+
+    /// Run after the response of the url including the responses short-circuited.
+    fn after_response(&self, _url: &str, _response: &mut PageResponse) {}
+}
+
+/// The middleware adding the headers returned by a callback to the request of the url. The headers
+/// returned replace the headers of the same name.
+pub struct RequestHeaders {
+    /// The callback getting the headers of the request of the url.
+    callback: Box<dyn Fn(&str) -> HeaderMap + Send + Sync>,
+}
+
+impl RequestHeaders {
+    /// Establish the middleware adding the headers returned by the callback.
+    pub fn new<F>(callback: F) -> Self
+    where
+        F: Fn(&str) -> HeaderMap + Send + Sync + 'static,
+    {
+        Self {
+            callback: Box::new(callback),
+        }
+    }
+}
+
+impl std::fmt::Debug for RequestHeaders {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.debug_struct("RequestHeaders").finish_non_exhaustive()
+    }
+}
+
+impl CrawlMiddleware for RequestHeaders {
+    fn before_request(&self, url: &str, headers: &mut HeaderMap) -> Option<PageResponse> {
+        headers.extend((self.callback)(url));
+        None
+    }
+}
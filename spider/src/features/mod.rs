@@ -1,6 +1,105 @@
+/// Asset downloads of a crawl
+pub mod assets;
+/// Bandwidth limit of the downloads of a crawl
+pub mod bandwidth;
+/// Bot challenge detection of the pages
+pub mod challenge;
+/// Distributed crawls with a coordinator leasing the links to the workers
+#[cfg(feature = "coordinator")]
+pub mod coordinator;
 /// Chrome utils
 #[cfg(feature = "chrome")]
 pub mod chrome;
+/// The crawls started in the background by the servers of a daemon
+#[cfg(any(feature = "grpc", feature = "server"))]
+pub(crate) mod daemon;
+/// Duplicate and near duplicate detection of the pages
+#[cfg(feature = "dedup")]
+pub mod dedup;
+/// Content change detection between crawls
+pub mod diff;
+/// Custom resolver of the hosts of a crawl
+#[cfg(feature = "dns")]
+pub mod dns;
+/// DNS cache of the hosts of a crawl
+pub mod dns_cache;
+/// RSS and Atom feeds of a crawl
+#[cfg(feature = "feeds")]
+pub mod feeds;
+/// gRPC service controlling the crawls of a long-lived daemon
+#[cfg(feature = "grpc")]
+pub mod grpc;
 /// URL globbing
 #[cfg(feature = "glob")]
 pub mod glob;
+/// The frontier of the links of a crawl with its disk spillover
+pub mod frontier;
+/// Language detection of the pages
+#[cfg(feature = "language")]
+pub mod language;
+/// The link graph of a crawl
+pub mod graph;
+/// HTTP Archive export of the requests of a crawl
+pub mod har;
+/// Recurring crawls of a website on a cron schedule
+#[cfg(feature = "cron")]
+pub mod job;
+/// Form login of a crawl before it starts
+#[cfg(feature = "cookies")]
+pub mod login;
+/// Hooks around the requests and responses of a crawl
+pub mod middleware;
+/// Markdown conversion of the pages
+#[cfg(feature = "markdown")]
+pub mod markdown;
+/// OAuth2 client credentials tokens of the requests of a crawl
+#[cfg(feature = "oauth2")]
+pub mod oauth2;
+/// Open Graph and Twitter Card metadata of the pages
+#[cfg(feature = "open_graph")]
+pub mod open_graph;
+/// Proxy rotation of the requests of a crawl
+pub mod proxies;
+/// Token bucket rate limit of the requests per host
+pub mod rate_limit;
+/// HTTP server controlling the crawls of a long-lived daemon
+#[cfg(feature = "server")]
+pub mod server;
+/// Request signing of the requests of a crawl
+#[cfg(feature = "signing")]
+pub mod signing;
+/// Output sinks publishing the pages of a crawl
+pub mod sink;
+/// SQLite persistence of the results of a crawl
+#[cfg(feature = "sqlite")]
+pub mod sqlite;
+/// Redis frontier and visited set shared by the crawls of several processes
+#[cfg(feature = "redis")]
+pub mod redis;
+/// Revisits of the pages crawled on the intervals of the revisit policy
+pub mod revisit;
+/// Statistics of a crawl
+pub mod stats;
+/// Structured data extraction of the pages
+#[cfg(feature = "structured_data")]
+pub mod structured_data;
+/// OpenTelemetry export of the spans of a crawl
+#[cfg(feature = "opentelemetry")]
+pub mod telemetry;
+/// Main content extraction of the pages
+#[cfg(feature = "text_extraction")]
+pub mod text_extraction;
+/// Timings of the requests of a crawl
+pub mod timings;
+/// Tor routing of the requests of a crawl
+#[cfg(feature = "tor")]
+pub mod tor;
+/// Incremental recrawls with the ETag and Last-Modified validators
+pub mod validators;
+/// Visited sets of the links of a crawl
+pub mod visited;
+/// Webhook notifications of the events of a crawl
+pub mod webhook;
+/// WARC output of the responses of a crawl
+#[cfg(feature = "warc")]
+pub mod warc;
@@ -0,0 +1,289 @@
+use crate::features::middleware::CrawlMiddleware;
+use crate::utils::{log, PageResponse};
+use compact_str::CompactString;
+use reqwest::header::{HeaderMap, HeaderValue, AUTHORIZATION};
+use reqwest::StatusCode;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// The time before the expiry of the token it is refreshed in the background.
+const REFRESH_LEEWAY: Duration = Duration::from_secs(60);
+
+/// The access token of the client.
+#[derive(Debug, Clone)]
+struct AccessToken {
+    /// The authorization header of the bearer token.
+    authorization: HeaderValue,
+    /// The time the token expires. None when the server did not send its lifetime.
+    expires_at: Option<Instant>,
+    /// The time the token was fetched.
+    fetched_at: Instant,
+}
+
+impl AccessToken {
+    /// The token expired.
+    fn is_expired(&self) -> bool {
+        match self.expires_at {
+            Some(expires_at) => Instant::now() >= expires_at,
+            _ => false,
+        }
+    }
+
+    /// The token expires soon and should be refreshed.
+    fn is_expiring(&self) -> bool {
+        match self.expires_at {
+            Some(expires_at) => Instant::now() + REFRESH_LEEWAY >= expires_at,
+            _ => false,
+        }
+    }
+}
+
+/// The OAuth2 client credentials grant fetching and refreshing the access token of the client.
+pub struct OAuth2ClientCredentials {
+    /// The token endpoint of the authorization server.
+    pub token_url: String,
+    /// The id of the client.
+    pub client_id: String,
+    /// The secret of the client sent with basic authentication.
+    client_secret: String,
+    /// The scope of the token requested. Defaults to None for the default scope of the client.
+    pub scope: Option<String>,
+    /// The client requesting the tokens.
+    client: reqwest::Client,
+    /// The access token fetched.
+    token: Mutex<Option<AccessToken>>,
+    /// The lock of the token fetched once by the concurrent requests.
+    refreshing: tokio::sync::Mutex<()>,
+    /// A refresh is spawned in the background.
+    refresh_pending: AtomicBool,
+}
+
+impl OAuth2ClientCredentials {
+    /// Establish the grant of the client with the token endpoint of the authorization server.
+    pub fn new(token_url: &str, client_id: &str, client_secret: &str) -> Self {
+        Self {
+            token_url: token_url.into(),
+            client_id: client_id.into(),
+            client_secret: client_secret.into(),
+            scope: None,
+            client: Default::default(),
+            token: Default::default(),
+            refreshing: Default::default(),
+            refresh_pending: AtomicBool::new(false),
+        }
+    }
+
+    /// Request the token with the scope like `read write`.
+    pub fn with_scope(&mut self, scope: Option<&str>) -> &mut Self {
+        self.scope = scope.map(|scope| scope.into());
+        self
+    }
+
+    /// Get the authorization header of the bearer token fetching the token when missing or
+    /// expired. None when the token cannot be fetched.
+    pub async fn get_token(&self) -> Option<HeaderValue> {
+        match self.get_cached_token() {
+            Some(token) if !token.is_expired() => return Some(token.authorization),
+            _ => (),
+        }
+
+        let _refreshing = self.refreshing.lock().await;
+
+        // the token fetched by another request while waiting
+        match self.get_cached_token() {
+            Some(token) if !token.is_expired() => Some(token.authorization),
+            _ => self.fetch_token().await,
+        }
+    }
+
+    /// Fetch a new token replacing the token cached. None when the token cannot be fetched.
+    pub async fn refresh(&self) -> Option<HeaderValue> {
+        let _refreshing = self.refreshing.lock().await;
+
+        self.fetch_token().await
+    }
+
+    /// Fetch a new token unless a token was fetched since the instant by another request. None
+    /// when the token cannot be fetched.
+    async fn refresh_since(&self, since: Instant) -> Option<HeaderValue> {
+        let _refreshing = self.refreshing.lock().await;
+
+        match self.get_cached_token() {
+            Some(token) if token.fetched_at >= since => Some(token.authorization),
+            _ => self.fetch_token().await,
+        }
+    }
+
+    /// Drop the token cached for the next requests to fetch a new token.
+    pub fn invalidate(&self) {
+        match self.token.lock() {
+            Ok(mut token) => *token = None,
+            _ => (),
+        }
+    }
+
+    /// Get the token cached.
+    fn get_cached_token(&self) -> Option<AccessToken> {
+        match self.token.lock() {
+            Ok(token) => token.clone(),
+            _ => None,
+        }
+    }
+
+    /// Fetch the token from the token endpoint caching it.
+    async fn fetch_token(&self) -> Option<HeaderValue> {
+        let mut params = vec![("grant_type", "client_credentials")];
+
+        match &self.scope {
+            Some(scope) => params.push(("scope", scope.as_str())),
+            _ => (),
+        }
+
+        let response = self
+            .client
+            .post(&self.token_url)
+            .basic_auth(&self.client_id, Some(&self.client_secret))
+            .form(&params)
+            .send()
+            .await
+            .and_then(|res| res.error_for_status());
+        let body = match response {
+            Ok(response) => response.bytes().await.unwrap_or_default(),
+            Err(e) => {
+                log("oauth2 token request failed", e.to_string());
+                return None;
+            }
+        };
+
+        match parse_token(&body) {
+            Some(token) => {
+                let authorization = token.authorization.clone();
+
+                match self.token.lock() {
+                    Ok(mut cached) => *cached = Some(token),
+                    _ => (),
+                }
+
+                Some(authorization)
+            }
+            _ => {
+                log("oauth2 token response invalid", &self.token_url);
+                None
+            }
+        }
+    }
+
+    /// Refresh the token in the background of the runtime once at a time.
+    fn refresh_in_background(self: &Arc<Self>) {
+        let handle = match tokio::runtime::Handle::try_current() {
+            Ok(handle) => handle,
+            _ => return,
+        };
+
+        if self.refresh_pending.swap(true, Ordering::Relaxed) {
+            return;
+        }
+
+        let oauth2 = self.clone();
+
+        handle.spawn(async move {
+            oauth2.refresh().await;
+            oauth2.refresh_pending.store(false, Ordering::Relaxed);
+        });
+    }
+}
+
+impl std::fmt::Debug for OAuth2ClientCredentials {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.debug_struct("OAuth2ClientCredentials")
+            .field("token_url", &self.token_url)
+            .field("client_id", &self.client_id)
+            .field("scope", &self.scope)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Parse the access token of the JSON response of the token endpoint.
+fn parse_token(body: &[u8]) -> Option<AccessToken> {
+    let response: serde_json::Value = serde_json::from_slice(body).ok()?;
+    let access_token = response.get("access_token")?.as_str()?;
+    let mut authorization = HeaderValue::from_str(&string_concat!("Bearer ", access_token)).ok()?;
+
+    authorization.set_sensitive(true);
+
+    Some(AccessToken {
+        authorization,
+        expires_at: response
+            .get("expires_in")
+            .and_then(|expires_in| expires_in.as_u64())
+            .map(|expires_in| Instant::now() + Duration::from_secs(expires_in)),
+        fetched_at: Instant::now(),
+    })
+}
+
+/// The middleware sending the bearer token of the grant to the requests of the host of the crawl.
+#[derive(Debug)]
+pub(crate) struct OAuth2Middleware {
+    /// The grant of the token.
+    oauth2: Arc<OAuth2ClientCredentials>,
+    /// The host of the crawl receiving the token.
+    host: CompactString,
+}
+
+impl OAuth2Middleware {
+    /// Establish the middleware sending the token of the grant to the host.
+    pub(crate) fn new(oauth2: Arc<OAuth2ClientCredentials>, host: &str) -> Self {
+        Self {
+            oauth2,
+            host: host.into(),
+        }
+    }
+
+    /// The url is on the host of the crawl.
+    fn is_host(&self, url: &str) -> bool {
+        match url::Url::parse(url) {
+            Ok(url) => url
+                .host_str()
+                .unwrap_or_default()
+                .eq_ignore_ascii_case(&self.host),
+            _ => false,
+        }
+    }
+}
+
+impl CrawlMiddleware for OAuth2Middleware {
+    fn before_request(&self, url: &str, headers: &mut HeaderMap) -> Option<PageResponse> {
+        if !self.is_host(url) {
+            return None;
+        }
+
+        match self.oauth2.get_cached_token() {
+            Some(token) if !token.is_expired() => {
+                if token.is_expiring() {
+                    self.oauth2.refresh_in_background();
+                }
+                headers.insert(AUTHORIZATION, token.authorization);
+            }
+            _ => self.oauth2.refresh_in_background(),
+        }
+
+        None
+    }
+
+    fn retry_request<'a>(
+        &'a self,
+        url: &'a str,
+        response: &'a PageResponse,
+    ) -> Pin<Box<dyn Future<Output = bool> + Send + 'a>> {
+        let received = Instant::now();
+
+        Box::pin(async move {
+            response.status_code == StatusCode::UNAUTHORIZED
+                && self.is_host(url)
+                && self.oauth2.refresh_since(received).await.is_some()
+        })
+    }
+}
@@ -0,0 +1,220 @@
+use crate::packages::scraper::Html;
+use crate::page::convert_abs_path;
+use url::Url;
+
+/// The Open Graph and Twitter Card metadata of a page.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct SocialMetadata {
+    /// The `og:*` meta tags.
+    pub open_graph: OpenGraph,
+    /// The `twitter:*` meta tags.
+    pub twitter_card: TwitterCard,
+}
+
+/// The Open Graph metadata of a page.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct OpenGraph {
+    /// The `og:title`.
+    pub title: Option<String>,
+    /// The `og:type` like `website` or `article`.
+    pub kind: Option<String>,
+    /// The absolute url of the `og:url`.
+    pub url: Option<Url>,
+    /// The `og:description`.
+    pub description: Option<String>,
+    /// The `og:site_name`.
+    pub site_name: Option<String>,
+    /// The `og:locale`.
+    pub locale: Option<String>,
+    /// The `og:image` images in order.
+    pub images: Vec<OpenGraphMedia>,
+    /// The `og:video` videos in order.
+    pub videos: Vec<OpenGraphMedia>,
+    /// The name without the `og:` prefix and the content of all the `og:*` meta tags in order.
+    pub properties: Vec<(String, String)>,
+}
+
+/// An image or a video of the Open Graph metadata.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OpenGraphMedia {
+    /// The absolute url of the media.
+    pub url: Url,
+    /// The absolute url of the `:secure_url`.
+    pub secure_url: Option<Url>,
+    /// The mime type of the `:type`.
+    pub mime_type: Option<String>,
+    /// The `:width` in pixels.
+    pub width: Option<u32>,
+    /// The `:height` in pixels.
+    pub height: Option<u32>,
+    /// The `:alt` text.
+    pub alt: Option<String>,
+}
+
+/// The Twitter Card metadata of a page.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct TwitterCard {
+    /// The `twitter:card` type like `summary` or `summary_large_image`.
+    pub card: Option<String>,
+    /// The `twitter:site` account of the website.
+    pub site: Option<String>,
+    /// The `twitter:creator` account of the author.
+    pub creator: Option<String>,
+    /// The `twitter:title`.
+    pub title: Option<String>,
+    /// The `twitter:description`.
+    pub description: Option<String>,
+    /// The absolute url of the `twitter:image`.
+    pub image: Option<Url>,
+    /// The `twitter:image:alt`.
+    pub image_alt: Option<String>,
+    /// The name without the `twitter:` prefix and the content of all the `twitter:*` meta tags
+    /// in order.
+    pub properties: Vec<(String, String)>,
+}
+
+impl SocialMetadata {
+    /// Get the Open Graph and Twitter Card metadata of the html parsed resolving the urls from the
+    /// base.
+    pub fn new(html: &Html, base: &Url) -> Self {
+        let mut social_metadata = Self::default();
+
+        for node in html.tree.values() {
+            match node.as_element() {
+                Some(element) if element.name() == "meta" => {
+                    // the tags are set with the property or the name attribute in the wild
+                    let name = element.attr("property").or_else(|| element.attr("name"));
+
+                    match (name, element.attr("content")) {
+                        (Some(name), Some(content)) => {
+                            let name = name.trim().to_ascii_lowercase();
+                            let content = content.trim();
+
+                            match name.strip_prefix("og:") {
+                                Some(property) => {
+                                    social_metadata.open_graph.insert(property, content, base)
+                                }
+                                _ => match name.strip_prefix("twitter:") {
+                                    Some(property) => {
+                                        social_metadata.twitter_card.insert(property, content, base)
+                                    }
+                                    _ => (),
+                                },
+                            }
+                        }
+                        _ => (),
+                    }
+                }
+                _ => (),
+            }
+        }
+
+        social_metadata
+    }
+}
+
+impl OpenGraph {
+    /// Set the property of the `og:*` meta tag keeping the first value of the single properties.
+    fn insert(&mut self, property: &str, content: &str, base: &Url) {
+        self.properties.push((property.into(), content.into()));
+
+        let field = match property {
+            "title" => &mut self.title,
+            "type" => &mut self.kind,
+            "description" => &mut self.description,
+            "site_name" => &mut self.site_name,
+            "locale" => &mut self.locale,
+            "url" => {
+                if self.url.is_none() {
+                    self.url = Some(convert_abs_path(base, content));
+                }
+                return;
+            }
+            _ => {
+                let (kind, structured) = match property.split_once(':') {
+                    Some((kind, structured)) => (kind, Some(structured)),
+                    _ => (property, None),
+                };
+                let media = match kind {
+                    "image" => &mut self.images,
+                    "video" => &mut self.videos,
+                    _ => return,
+                };
+
+                match structured {
+                    None | Some("url") => {
+                        let url = convert_abs_path(base, content);
+
+                        // the `:url` repeats the url of the media before it on some pages
+                        match media.last() {
+                            Some(last) if structured.is_some() && last.url == url => (),
+                            _ => media.push(OpenGraphMedia::new(url)),
+                        }
+                    }
+                    Some(structured) => match media.last_mut() {
+                        Some(last) => last.insert(structured, content, base),
+                        _ => (),
+                    },
+                }
+                return;
+            }
+        };
+
+        if field.is_none() {
+            *field = Some(content.into());
+        }
+    }
+}
+
+impl OpenGraphMedia {
+    /// Establish a new media from its absolute url.
+    pub fn new(url: Url) -> Self {
+        Self {
+            url,
+            secure_url: None,
+            mime_type: None,
+            width: None,
+            height: None,
+            alt: None,
+        }
+    }
+
+    /// Set the structured property of the media.
+    fn insert(&mut self, structured: &str, content: &str, base: &Url) {
+        match structured {
+            "secure_url" => self.secure_url = Some(convert_abs_path(base, content)),
+            "type" => self.mime_type = Some(content.into()),
+            "width" => self.width = content.parse().ok(),
+            "height" => self.height = content.parse().ok(),
+            "alt" => self.alt = Some(content.into()),
+            _ => (),
+        }
+    }
+}
+
+impl TwitterCard {
+    /// Set the property of the `twitter:*` meta tag keeping the first value.
+    fn insert(&mut self, property: &str, content: &str, base: &Url) {
+        self.properties.push((property.into(), content.into()));
+
+        let field = match property {
+            "card" => &mut self.card,
+            "site" => &mut self.site,
+            "creator" => &mut self.creator,
+            "title" => &mut self.title,
+            "description" => &mut self.description,
+            "image:alt" => &mut self.image_alt,
+            "image" | "image:src" => {
+                if self.image.is_none() {
+                    self.image = Some(convert_abs_path(base, content));
+                }
+                return;
+            }
+            _ => return,
+        };
+
+        if field.is_none() {
+            *field = Some(content.into());
+        }
+    }
+}
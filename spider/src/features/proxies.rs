@@ -0,0 +1,272 @@
+use crate::configuration::{ProxyRotation, ProxyStrategy};
+#[cfg(feature = "tor")]
+use crate::features::tor::TorControl;
+use crate::utils::get_proxy_redacted;
+use compact_str::CompactString;
+use hashbrown::HashMap;
+use reqwest::{Client, StatusCode};
+use std::sync::atomic::{AtomicUsize, Ordering};
+#[cfg(feature = "tor")]
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::time::Instant;
+use url::Url;
+
+/// The health of a proxy of the rotation.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct ProxyStats {
+    /// The url of the proxy without the password of its user info.
+    pub proxy: String,
+    /// The requests sent through the proxy.
+    pub requests: u64,
+    /// The requests failed from the proxy.
+    pub failures: u64,
+    /// The failures of the proxy since its last request succeeded.
+    pub consecutive_failures: u32,
+    /// The proxy is evicted from the rotation.
+    pub evicted: bool,
+}
+
+/// The health of a proxy with the time it was evicted.
+#[derive(Debug, Default)]
+struct ProxyState {
+    /// The health of the proxy.
+    stats: ProxyStats,
+    /// The time the proxy was evicted.
+    evicted_at: Option<Instant>,
+}
+
+/// A proxy of the rotation with its clients.
+#[derive(Debug)]
+struct ProxyClient {
+    /// The client of the requests through the proxy.
+    client: Client,
+    /// The client sending the credentials to the host of the crawl through the proxy.
+    auth_client: Option<Box<(Client, CompactString)>>,
+    /// The health of the proxy.
+    state: Mutex<ProxyState>,
+}
+
+/// The proxies rotated shared across the tasks of the crawl.
+#[derive(Debug)]
+pub struct ProxyPool {
+    /// The rotation of the proxies.
+    rotation: ProxyRotation,
+    /// The proxies in the order of the configuration.
+    proxies: Vec<ProxyClient>,
+    /// The count of the proxies selected round-robin.
+    next: AtomicUsize,
+    /// The proxy of the hosts with the sticky strategy.
+    sticky: Mutex<HashMap<CompactString, usize>>,
+    #[cfg(feature = "tor")]
+    /// The controller of the circuits when the proxy is Tor.
+    tor: Option<Arc<TorControl>>,
+}
+
+impl ProxyPool {
+    /// Establish a new empty pool with the rotation.
+    pub fn new(rotation: ProxyRotation) -> Self {
+        Self {
+            rotation,
+            proxies: Default::default(),
+            next: Default::default(),
+            sticky: Default::default(),
+            #[cfg(feature = "tor")]
+            tor: None,
+        }
+    }
+
+    #[cfg(feature = "tor")]
+    /// Set the controller of the circuits of Tor to rotate on the responses recorded.
+    pub fn with_tor(&mut self, tor: Option<Arc<TorControl>>) -> &mut Self {
+        self.tor = tor;
+        self
+    }
+
+    #[cfg(feature = "tor")]
+    /// Get the controller of the circuits of Tor.
+    pub fn get_tor(&self) -> Option<&Arc<TorControl>> {
+        self.tor.as_ref()
+    }
+
+    /// Add a proxy with its clients to the rotation.
+    pub fn push(
+        &mut self,
+        proxy: &str,
+        client: Client,
+        auth_client: Option<Box<(Client, CompactString)>>,
+    ) {
+        self.proxies.push(ProxyClient {
+            client,
+            auth_client,
+            state: Mutex::new(ProxyState {
+                stats: ProxyStats {
+                    proxy: get_proxy_redacted(proxy),
+                    ..Default::default()
+                },
+                evicted_at: None,
+            }),
+        });
+    }
+
+    /// The pool has no proxies.
+    pub fn is_empty(&self) -> bool {
+        self.proxies.is_empty()
+    }
+
+    /// Select the proxy of the request to the url. Returns `None` when every proxy is evicted.
+    pub fn select(&self, url: &str) -> Option<usize> {
+        match self.rotation.strategy {
+            ProxyStrategy::RoundRobin => self.next_available(),
+            ProxyStrategy::Random if !self.is_empty() => {
+                let start = fastrand::usize(..self.proxies.len());
+
+                (0..self.proxies.len())
+                    .map(|i| (start + i) % self.proxies.len())
+                    .find(|index| self.is_available(*index))
+            }
+            ProxyStrategy::Random => None,
+            ProxyStrategy::Sticky => {
+                let host: CompactString = match Url::parse(url) {
+                    Ok(u) => u.host_str().unwrap_or_default().to_ascii_lowercase().into(),
+                    _ => Default::default(),
+                };
+                let mut sticky = self.sticky.lock().ok()?;
+
+                match sticky.get(&host) {
+                    Some(index) if self.is_available(*index) => Some(*index),
+                    _ => {
+                        let index = self.next_available()?;
+                        sticky.insert(host, index);
+                        Some(index)
+                    }
+                }
+            }
+        }
+    }
+
+    /// Get the client and the client with the credentials of the proxy.
+    pub fn get_clients(&self, index: usize) -> (&Client, &Option<Box<(Client, CompactString)>>) {
+        let proxy = &self.proxies[index];
+
+        (&proxy.client, &proxy.auth_client)
+    }
+
+    /// Record the response of a request through the proxy evicting the proxy after the consecutive
+    /// failures of the rotation. The requests failed without a response and the 407, 502, and 504
+    /// statuses are failures of the proxy.
+    pub fn record(&self, index: usize, status_code: StatusCode, request_failed: bool) {
+        let failed = request_failed
+            || status_code == StatusCode::PROXY_AUTHENTICATION_REQUIRED
+            || status_code == StatusCode::BAD_GATEWAY
+            || status_code == StatusCode::GATEWAY_TIMEOUT;
+
+        match self.proxies.get(index).map(|proxy| proxy.state.lock()) {
+            Some(Ok(mut state)) => {
+                state.stats.requests += 1;
+
+                if failed {
+                    state.stats.failures += 1;
+                    state.stats.consecutive_failures += 1;
+
+                    if !state.stats.evicted
+                        && state.stats.consecutive_failures >= self.rotation.max_failures
+                    {
+                        state.stats.evicted = true;
+                        state.evicted_at = Some(Instant::now());
+                    }
+                } else {
+                    state.stats.consecutive_failures = 0;
+                }
+            }
+            _ => (),
+        }
+    }
+
+    /// Get the health of the proxies in the order of the configuration.
+    pub fn get_stats(&self) -> Vec<ProxyStats> {
+        self.proxies
+            .iter()
+            .filter_map(|proxy| proxy.state.lock().ok().map(|state| state.stats.clone()))
+            .collect()
+    }
+
+    /// Select the next proxy not evicted round-robin.
+    fn next_available(&self) -> Option<usize> {
+        (0..self.proxies.len())
+            .map(|_| self.next.fetch_add(1, Ordering::Relaxed) % self.proxies.len())
+            .find(|index| self.is_available(*index))
+    }
+
+    /// The proxy is not evicted or its eviction is over restoring it to the rotation.
+    fn is_available(&self, index: usize) -> bool {
+        match self.proxies[index].state.lock() {
+            Ok(mut state) => match (state.evicted_at, self.rotation.eviction) {
+                (None, _) => true,
+                (Some(evicted_at), Some(eviction)) if evicted_at.elapsed() >= eviction => {
+                    state.evicted_at = None;
+                    state.stats.evicted = false;
+                    state.stats.consecutive_failures = 0;
+                    true
+                }
+                _ => false,
+            },
+            _ => false,
+        }
+    }
+}
+
+#[test]
+fn test_proxy_rotation() {
+    let mut proxy_pool = ProxyPool::new(Default::default());
+    proxy_pool.push("http://10.1.1.1:8080", Client::new(), None);
+    proxy_pool.push("http://10.1.1.2:8080", Client::new(), None);
+
+    let link = "https://choosealicense.com/licenses/";
+
+    assert_eq!(proxy_pool.select(link), Some(0));
+    assert_eq!(proxy_pool.select(link), Some(1));
+
+    for _ in 0..3 {
+        proxy_pool.record(0, StatusCode::BAD_GATEWAY, false);
+    }
+    proxy_pool.record(1, StatusCode::OK, false);
+
+    assert_eq!(proxy_pool.select(link), Some(1));
+    assert_eq!(proxy_pool.select(link), Some(1));
+
+    let stats = proxy_pool.get_stats();
+
+    assert_eq!(stats[0].proxy, "http://10.1.1.1:8080");
+    assert_eq!((stats[0].requests, stats[0].failures), (3, 3));
+    assert!(stats[0].evicted);
+    assert_eq!((stats[1].requests, stats[1].failures), (1, 0));
+
+    for _ in 0..3 {
+        proxy_pool.record(1, StatusCode::OK, true);
+    }
+
+    assert_eq!(proxy_pool.select(link), None);
+
+    let mut proxy_pool = ProxyPool::new(ProxyRotation {
+        strategy: ProxyStrategy::Sticky,
+        max_failures: 1,
+        eviction: Some(std::time::Duration::ZERO),
+    });
+    proxy_pool.push("http://10.1.1.1:8080", Client::new(), None);
+    proxy_pool.push("http://10.1.1.2:8080", Client::new(), None);
+
+    let sticky = proxy_pool.select(link);
+
+    assert_eq!(
+        proxy_pool.select("https://choosealicense.com/about/"),
+        sticky
+    );
+    assert_ne!(proxy_pool.select("https://github.com/"), sticky);
+
+    proxy_pool.record(sticky.unwrap(), StatusCode::OK, true);
+
+    assert!(proxy_pool.get_stats()[sticky.unwrap()].evicted);
+    assert_eq!(proxy_pool.select(link), sticky);
+    assert!(!proxy_pool.get_stats()[sticky.unwrap()].evicted);
+}
@@ -0,0 +1,39 @@
+use crate::configuration::RateLimit;
+
+/// The tokens of the rate limit of a host.
+#[derive(Debug)]
+pub(crate) struct TokenBucket {
+    /// The tokens left, negative with the requests reserved ahead of the refill.
+    tokens: f64,
+    /// The time the tokens were refilled at.
+    refilled: tokio::time::Instant,
+}
+
+impl TokenBucket {
+    /// Establish the bucket full with the burst of the rate limit.
+    pub(crate) fn new(rate_limit: &RateLimit, now: tokio::time::Instant) -> Self {
+        Self {
+            tokens: rate_limit.burst.max(1).into(),
+            refilled: now,
+        }
+    }
+
+    /// Take a token refilling the bucket up to the burst. Returns the time to dispatch the request
+    /// at when the token is refilled.
+    pub(crate) fn reserve(
+        &mut self,
+        rate_limit: &RateLimit,
+        now: tokio::time::Instant,
+    ) -> tokio::time::Instant {
+        if now > self.refilled {
+            let refill = (now - self.refilled).as_secs_f64() * rate_limit.requests_per_second;
+
+            self.tokens = (self.tokens + refill).min(rate_limit.burst.max(1).into());
+            self.refilled = now;
+        }
+
+        self.tokens -= 1.0;
+
+        now + rate_limit.get_refill_time(-self.tokens)
+    }
+}
@@ -0,0 +1,201 @@
+use crate::features::frontier::{SharedFrontier, SpilledLink};
+use crate::features::visited::VisitedSet;
+use crate::CaseInsensitiveString;
+use std::sync::Mutex;
+
+/// The connection to Redis established on the first command.
+struct RedisConnection {
+    /// The client of the server.
+    client: redis::Client,
+    /// The connection established.
+    connection: Mutex<Option<redis::Connection>>,
+}
+
+impl std::fmt::Debug for RedisConnection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RedisConnection")
+            .field("client", &self.client)
+            .finish()
+    }
+}
+
+impl RedisConnection {
+    /// Establish the client of the server of the url like `redis://127.0.0.1/`.
+    fn new(url: &str) -> redis::RedisResult<Self> {
+        Ok(Self {
+            client: redis::Client::open(url)?,
+            connection: Mutex::new(None),
+        })
+    }
+
+    /// Run the commands on the connection established. The connection is dropped when the
+    /// commands fail to re-establish it on the next commands.
+    fn query<T>(
+        &self,
+        query: impl FnOnce(&mut redis::Connection) -> redis::RedisResult<T>,
+    ) -> Option<T> {
+        let mut connection = self.connection.lock().ok()?;
+
+        if connection.is_none() {
+            *connection = self.client.get_connection().ok();
+        }
+
+        match query(connection.as_mut()?) {
+            Ok(value) => Some(value),
+            _ => {
+                *connection = None;
+                None
+            }
+        }
+    }
+}
+
+/// The links queued to crawl in a Redis sorted set shared by the crawls of several processes.
+#[derive(Debug)]
+pub struct RedisFrontier {
+    /// The connection to the server.
+    connection: RedisConnection,
+    /// The key of the sorted set of the links queued.
+    key: String,
+}
+
+impl RedisFrontier {
+    /// Establish the frontier of the sorted set of the key on the server of the url like
+    /// `redis://127.0.0.1/`. The connection is established on the first command.
+    pub fn new(url: &str, key: &str) -> redis::RedisResult<Self> {
+        Ok(Self {
+            connection: RedisConnection::new(url)?,
+            key: key.into(),
+        })
+    }
+}
+
+impl SharedFrontier for RedisFrontier {
+    fn push(&self, links: &[SpilledLink]) {
+        if links.is_empty() {
+            return;
+        }
+
+        // the members are the links with their depth as `depth link`
+        let _ = self.connection.query(|connection| {
+            let mut pipe = redis::pipe();
+
+            for link in links {
+                pipe.cmd("ZADD")
+                    .arg(&self.key)
+                    .arg("NX")
+                    .arg(link.priority)
+                    .arg(format!("{} {}", link.depth, link.link.inner()))
+                    .ignore();
+            }
+
+            pipe.query::<()>(connection)
+        });
+    }
+
+    fn pop(&self, max: usize) -> Vec<SpilledLink> {
+        let popped: Vec<(String, f64)> = self
+            .connection
+            .query(|connection| {
+                redis::cmd("ZPOPMAX")
+                    .arg(&self.key)
+                    .arg(max.max(1))
+                    .query(connection)
+            })
+            .unwrap_or_default();
+
+        popped
+            .into_iter()
+            .filter_map(|(member, priority)| {
+                let (depth, link) = member.split_once(' ')?;
+
+                Some(SpilledLink {
+                    link: link.into(),
+                    depth: depth.parse().ok()?,
+                    priority: priority as u32,
+                })
+            })
+            .collect()
+    }
+
+    fn len(&self) -> usize {
+        self.connection
+            .query(|connection| redis::cmd("ZCARD").arg(&self.key).query(connection))
+            .unwrap_or_default()
+    }
+
+    fn clear(&self) {
+        let _: Option<()> = self
+            .connection
+            .query(|connection| redis::cmd("DEL").arg(&self.key).query(connection));
+    }
+}
+
+/// The links visited in a Redis set shared by the crawls of several processes claiming a link for
+/// a single crawl.
+#[derive(Debug)]
+pub struct RedisVisitedSet {
+    /// The connection to the server.
+    connection: RedisConnection,
+    /// The key of the set of the links visited.
+    key: String,
+}
+
+impl RedisVisitedSet {
+    /// Establish the visited set of the set of the key on the server of the url like
+    /// `redis://127.0.0.1/`. The connection is established on the first command.
+    pub fn new(url: &str, key: &str) -> redis::RedisResult<Self> {
+        Ok(Self {
+            connection: RedisConnection::new(url)?,
+            key: key.into(),
+        })
+    }
+}
+
+impl VisitedSet for RedisVisitedSet {
+    fn insert(&self, link: &CaseInsensitiveString) -> bool {
+        self.connection
+            .query(|connection| {
+                redis::cmd("SADD")
+                    .arg(&self.key)
+                    .arg(link.inner().to_lowercase())
+                    .query::<usize>(connection)
+            })
+            // the links are claimed while the server is unreachable to keep crawling
+            .map_or(true, |added| added > 0)
+    }
+
+    fn contains(&self, link: &CaseInsensitiveString) -> bool {
+        self.connection
+            .query(|connection| {
+                redis::cmd("SISMEMBER")
+                    .arg(&self.key)
+                    .arg(link.inner().to_lowercase())
+                    .query::<bool>(connection)
+            })
+            .unwrap_or_default()
+    }
+
+    fn remove(&self, link: &CaseInsensitiveString) -> bool {
+        self.connection
+            .query(|connection| {
+                redis::cmd("SREM")
+                    .arg(&self.key)
+                    .arg(link.inner().to_lowercase())
+                    .query::<usize>(connection)
+            })
+            .map_or(false, |removed| removed > 0)
+    }
+
+    fn len(&self) -> usize {
+        self.connection
+            .query(|connection| redis::cmd("SCARD").arg(&self.key).query(connection))
+            .unwrap_or_default()
+    }
+
+    fn clear(&self) {
+        let _: Option<()> = self
+            .connection
+            .query(|connection| redis::cmd("DEL").arg(&self.key).query(connection));
+    }
+}
@@ -0,0 +1,172 @@
+use crate::configuration::RevisitPolicy;
+use crate::CaseInsensitiveString;
+use hashbrown::HashMap;
+use std::collections::BinaryHeap;
+use std::time::Duration;
+
+/// A link scheduled to revisit.
+#[derive(Debug, PartialEq, Eq)]
+struct DueLink {
+    /// The time the link is due, the earliest is popped first.
+    due: std::cmp::Reverse<tokio::time::Instant>,
+    /// The link to revisit.
+    link: CaseInsensitiveString,
+}
+
+impl Ord for DueLink {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.due.cmp(&other.due)
+    }
+}
+
+impl PartialOrd for DueLink {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// The revisit state of a link.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Revisit {
+    /// The interval to revisit the link.
+    interval: Duration,
+    /// The link hops from the start url.
+    depth: usize,
+    /// The digest of the page content on the last visit.
+    digest: Option<u64>,
+}
+
+/// The links revisited when due on the intervals of the revisit policy adapted to the changes of
+/// their pages.
+#[derive(Debug, Default)]
+pub(crate) struct Revisits {
+    /// The intervals to revisit the links.
+    pub(crate) policy: RevisitPolicy,
+    /// The links scheduled ordered by the time due.
+    queue: BinaryHeap<DueLink>,
+    /// The revisit state per link.
+    pub(crate) links: HashMap<CaseInsensitiveString, Revisit>,
+}
+
+impl Revisits {
+    /// Schedule the link found at the depth due at the time when not scheduled yet.
+    pub(crate) fn schedule(
+        &mut self,
+        link: CaseInsensitiveString,
+        depth: usize,
+        due: tokio::time::Instant,
+    ) {
+        if !self.links.contains_key(&link) {
+            self.links.insert(
+                link.clone(),
+                Revisit {
+                    interval: self.policy.get_interval(link.inner()),
+                    depth,
+                    digest: None,
+                },
+            );
+            self.queue.push(DueLink {
+                due: std::cmp::Reverse(due),
+                link,
+            });
+        }
+    }
+
+    /// Pop the links due at the time with their depth.
+    pub(crate) fn pop_due(
+        &mut self,
+        now: tokio::time::Instant,
+    ) -> Vec<(CaseInsensitiveString, usize)> {
+        let mut due = Vec::new();
+
+        while self.queue.peek().map_or(false, |link| link.due.0 <= now) {
+            match self.queue.pop() {
+                Some(link) => match self.links.get(&link.link) {
+                    Some(revisit) => due.push((link.link, revisit.depth)),
+                    _ => (),
+                },
+                _ => (),
+            }
+        }
+
+        due
+    }
+
+    /// Record the digest of the page revisited, None when unchanged or failed, and schedule the
+    /// next revisit of the link from the time. Returns true when the page changed since the last
+    /// visit. The first digest of a link does not adapt its interval.
+    pub(crate) fn record(
+        &mut self,
+        link: CaseInsensitiveString,
+        digest: Option<u64>,
+        now: tokio::time::Instant,
+    ) -> bool {
+        match self.links.get_mut(&link) {
+            Some(revisit) => {
+                let changed = digest.is_some() && digest != revisit.digest;
+
+                if revisit.digest.is_some() || !changed {
+                    revisit.interval = self.policy.get_next_interval(revisit.interval, changed);
+                }
+                if changed {
+                    revisit.digest = digest;
+                }
+
+                self.queue.push(DueLink {
+                    due: std::cmp::Reverse(now + revisit.interval),
+                    link,
+                });
+
+                changed
+            }
+            _ => false,
+        }
+    }
+}
+
+#[test]
+fn test_revisits() {
+    let mut revisits = Revisits {
+        policy: RevisitPolicy {
+            interval: Duration::from_secs(60),
+            patterns: vec![("/blog/".into(), Duration::from_secs(10))],
+            adaptive: true,
+            interval_min: Duration::from_secs(5),
+            interval_max: Duration::from_secs(120),
+        },
+        ..Default::default()
+    };
+    let link: CaseInsensitiveString = "https://choosealicense.com/blog/".into();
+    let other: CaseInsensitiveString = "https://choosealicense.com/about/".into();
+    let now = tokio::time::Instant::now();
+
+    revisits.schedule(link.clone(), 1, now);
+    revisits.schedule(other.clone(), 2, now + Duration::from_secs(60));
+    revisits.schedule(link.clone(), 3, now + Duration::from_secs(60));
+
+    assert_eq!(revisits.pop_due(now), vec![(link.clone(), 1)]);
+    assert!(revisits.pop_due(now).is_empty());
+
+    // the first digest is recorded as a change without adapting the interval
+    assert!(revisits.record(link.clone(), Some(1), now));
+    assert_eq!(revisits.links[&link].interval, Duration::from_secs(10));
+    assert!(revisits.pop_due(now + Duration::from_secs(9)).is_empty());
+    assert_eq!(
+        revisits.pop_due(now + Duration::from_secs(10)),
+        vec![(link.clone(), 1)]
+    );
+
+    assert!(!revisits.record(link.clone(), Some(1), now));
+    assert_eq!(revisits.links[&link].interval, Duration::from_secs(20));
+    revisits.pop_due(now + Duration::from_secs(20));
+
+    assert!(revisits.record(link.clone(), Some(2), now));
+    assert_eq!(revisits.links[&link].interval, Duration::from_secs(10));
+    revisits.pop_due(now + Duration::from_secs(10));
+
+    assert!(!revisits.record(link.clone(), None, now));
+    assert_eq!(
+        revisits.pop_due(now + Duration::from_secs(60)),
+        vec![(link, 1), (other, 2)]
+    );
+}
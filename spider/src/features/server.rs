@@ -0,0 +1,256 @@
+use crate::features::daemon::{CrawlEntry, CrawlRegistry};
+use crate::utils::log;
+use crate::website::Website;
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use axum::routing::get;
+use axum::{Json, Router};
+use compact_str::CompactString;
+use serde::{Deserialize, Serialize};
+use std::net::SocketAddr;
+use std::sync::Arc;
+use tokio::sync::{broadcast, mpsc};
+use tokio_stream::wrappers::ReceiverStream;
+
+/// The website to crawl and its configuration. The fields left out use the defaults.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CrawlConfig {
+    /// The start url of the crawl.
+    pub url: String,
+    /// The max pages crawled. Use 0 for no limit.
+    pub limit: usize,
+    /// The max depth of the links followed. Use 0 for no limit.
+    pub depth: usize,
+    /// The delay between the requests in milliseconds.
+    pub delay: u64,
+    /// Crawl the subdomains of the website.
+    pub subdomains: bool,
+    /// Respect the robots.txt of the website.
+    pub respect_robots_txt: bool,
+    /// The user agent of the requests.
+    pub user_agent: Option<String>,
+    /// The links to skip.
+    pub blacklist_url: Vec<String>,
+}
+
+impl CrawlConfig {
+    /// Get the website of the configuration to crawl.
+    pub fn get_website(&self) -> Website {
+        let mut website: Website = Website::new(&self.url);
+
+        website
+            .with_limit(self.limit)
+            .with_depth(self.depth)
+            .with_delay(self.delay)
+            .with_subdomains(self.subdomains)
+            .with_respect_robots_txt(self.respect_robots_txt)
+            .with_user_agent(self.user_agent.as_deref());
+
+        if !self.blacklist_url.is_empty() {
+            let blacklist_url: Vec<CompactString> =
+                self.blacklist_url.iter().map(|url| url.into()).collect();
+
+            website.with_blacklist_url(Some(blacklist_url));
+        }
+
+        website
+    }
+}
+
+/// The id of a crawl started.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CrawlStarted {
+    /// The id of the crawl.
+    pub id: String,
+}
+
+/// The status and the totals of a crawl.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CrawlStatusResponse {
+    /// The id of the crawl.
+    pub id: String,
+    /// The start url of the crawl.
+    pub url: String,
+    /// The status of the crawl like Active, Idle, or Blocked.
+    pub status: String,
+    /// The crawl ended.
+    pub finished: bool,
+    /// The crawl was cancelled.
+    pub cancelled: bool,
+    /// The pages crawled.
+    pub pages: u64,
+    /// The requests failed.
+    pub failures: u64,
+}
+
+impl CrawlStatusResponse {
+    /// Get the status of the crawl of the id.
+    fn new(id: String, crawl: &CrawlEntry) -> Self {
+        Self {
+            id,
+            url: crawl.url.clone(),
+            status: crawl.get_status(),
+            finished: crawl.is_finished(),
+            cancelled: crawl.is_cancelled(),
+            pages: crawl.get_pages(),
+            failures: crawl.get_failures(),
+        }
+    }
+}
+
+/// The results of a crawl.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CrawlResults {
+    /// The status and the totals of the crawl.
+    #[serde(flatten)]
+    pub status: CrawlStatusResponse,
+    /// The links visited by the crawl. Empty until the crawl ended.
+    pub links: Vec<String>,
+}
+
+/// A page crawled sent by the stream of a crawl.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PageEvent {
+    /// The url of the page.
+    pub url: String,
+    /// The status code of the response.
+    pub status_code: u16,
+    /// The request failed from a network or server error or a bot challenge not solved.
+    pub failed: bool,
+}
+
+/// The error of a request to the server.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ErrorResponse {
+    /// The message of the error.
+    pub error: String,
+}
+
+/// Reply the error with the status code.
+fn error_response(status_code: StatusCode, error: String) -> Response {
+    (status_code, Json(ErrorResponse { error })).into_response()
+}
+
+/// Get the crawl of the id replying not found without it.
+fn get_crawl(crawls: &CrawlRegistry, id: &str) -> Result<Arc<CrawlEntry>, Response> {
+    crawls
+        .get(id)
+        .ok_or_else(|| error_response(StatusCode::NOT_FOUND, format!("crawl {} not found", id)))
+}
+
+/// Start the crawl of the configuration.
+async fn start_crawl(
+    State(crawls): State<Arc<CrawlRegistry>>,
+    Json(config): Json<CrawlConfig>,
+) -> Response {
+    if url::Url::parse(&config.url).is_err() {
+        return error_response(
+            StatusCode::BAD_REQUEST,
+            format!("invalid url {}", config.url),
+        );
+    }
+
+    match crawls.start(config.get_website()) {
+        Some(id) => (StatusCode::CREATED, Json(CrawlStarted { id })).into_response(),
+        _ => error_response(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "the crawl could not be started".into(),
+        ),
+    }
+}
+
+/// Get the status of the crawl.
+async fn get_status(State(crawls): State<Arc<CrawlRegistry>>, Path(id): Path<String>) -> Response {
+    match get_crawl(&crawls, &id) {
+        Ok(crawl) => Json(CrawlStatusResponse::new(id, &crawl)).into_response(),
+        Err(response) => response,
+    }
+}
+
+/// Get the results of the crawl.
+async fn get_results(State(crawls): State<Arc<CrawlRegistry>>, Path(id): Path<String>) -> Response {
+    match get_crawl(&crawls, &id) {
+        Ok(crawl) => Json(CrawlResults {
+            links: crawl.get_links(),
+            status: CrawlStatusResponse::new(id, &crawl),
+        })
+        .into_response(),
+        Err(response) => response,
+    }
+}
+
+/// Stream the pages of the crawl as newline delimited JSON.
+async fn stream_pages(
+    State(crawls): State<Arc<CrawlRegistry>>,
+    Path(id): Path<String>,
+) -> Response {
+    let crawl = match get_crawl(&crawls, &id) {
+        Ok(crawl) => crawl,
+        Err(response) => return response,
+    };
+    let mut pages = crawl.subscribe();
+    let (sender, receiver) =
+        mpsc::channel::<Result<String, std::convert::Infallible>>(crawls.capacity);
+
+    tokio::spawn(async move {
+        loop {
+            match pages.recv().await {
+                Ok(page) => {
+                    let event = PageEvent {
+                        url: page.get_url().to_string(),
+                        status_code: page.status_code.as_u16(),
+                        failed: page.is_failed(),
+                    };
+                    let mut line = serde_json::to_string(&event).unwrap_or_default();
+
+                    line.push('\n');
+
+                    // the stream was dropped by the client
+                    if sender.send(Ok(line)).await.is_err() {
+                        break;
+                    }
+                }
+                Err(broadcast::error::RecvError::Lagged(_)) => (),
+                _ => break,
+            }
+        }
+    });
+
+    (
+        [(axum::http::header::CONTENT_TYPE, "application/x-ndjson")],
+        axum::body::StreamBody::new(ReceiverStream::new(receiver)),
+    )
+        .into_response()
+}
+
+/// Cancel the crawl.
+async fn cancel(State(crawls): State<Arc<CrawlRegistry>>, Path(id): Path<String>) -> Response {
+    match get_crawl(&crawls, &id) {
+        Ok(crawl) => {
+            crawl.cancel();
+            Json(CrawlStatusResponse::new(id, &crawl)).into_response()
+        }
+        Err(response) => response,
+    }
+}
+
+/// Get the router of the API with its own crawls to serve or to nest in your own router.
+pub fn router() -> Router {
+    Router::new()
+        .route("/crawls", axum::routing::post(start_crawl))
+        .route("/crawls/:id", get(get_status).delete(cancel))
+        .route("/crawls/:id/results", get(get_results))
+        .route("/crawls/:id/stream", get(stream_pages))
+        .with_state(Arc::new(CrawlRegistry::default()))
+}
+
+/// Serve the API on the address until the server fails.
+pub async fn serve(addr: SocketAddr) -> Result<(), hyper::Error> {
+    log("HTTP server listening", addr.to_string());
+
+    axum::Server::bind(&addr)
+        .serve(router().into_make_service())
+        .await
+}
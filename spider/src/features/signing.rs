@@ -0,0 +1,337 @@
+use crate::features::middleware::CrawlMiddleware;
+use crate::utils::get_iso_date;
+use hmac::{Hmac, Mac};
+use percent_encoding::{percent_decode_str, utf8_percent_encode, AsciiSet, NON_ALPHANUMERIC};
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue, AUTHORIZATION};
+use reqwest::Request;
+use sha2::{Digest, Sha256};
+use std::time::SystemTime;
+use url::Url;
+
+/// The characters encoded by the AWS signatures, all but the unreserved characters.
+const AWS_ENCODE_SET: &AsciiSet = &NON_ALPHANUMERIC
+    .remove(b'-')
+    .remove(b'_')
+    .remove(b'.')
+    .remove(b'~');
+
+/// The algorithm of the AWS Signature Version 4.
+const SIGV4_ALGORITHM: &str = "AWS4-HMAC-SHA256";
+
+/// The signer adding the HMAC-SHA256 of the requests keyed by a shared secret. The signature is
+/// the HMAC in hex of the lines of the method, the path and the query, the timestamp in seconds
+/// since the epoch, and the SHA-256 in hex of the body.
+#[derive(Clone)]
+pub struct HmacSigner {
+    /// The shared secret of the HMAC.
+    pub key: Vec<u8>,
+    /// The header of the signature. Defaults to `x-signature`.
+    pub signature_header: HeaderName,
+    /// The header of the timestamp signed. Defaults to `x-timestamp`.
+    pub timestamp_header: HeaderName,
+}
+
+impl HmacSigner {
+    /// Establish the signer with the shared secret.
+    pub fn new(key: &[u8]) -> Self {
+        Self {
+            key: key.to_vec(),
+            signature_header: HeaderName::from_static("x-signature"),
+            timestamp_header: HeaderName::from_static("x-timestamp"),
+        }
+    }
+
+    /// Sign the request of the method to the url with the body sent at the time adding the
+    /// signature and the timestamp to the headers.
+    pub fn sign(
+        &self,
+        method: &str,
+        url: &str,
+        body: &[u8],
+        time: SystemTime,
+        headers: &mut HeaderMap,
+    ) {
+        let url = match Url::parse(url) {
+            Ok(url) => url,
+            _ => return,
+        };
+        let timestamp = time
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs()
+            .to_string();
+        let message = format!(
+            "{}\n{}\n{}\n{}",
+            method,
+            &url[url::Position::BeforePath..url::Position::AfterQuery],
+            timestamp,
+            get_hex(&Sha256::digest(body))
+        );
+        let signature = get_hex(&get_hmac(&self.key, message.as_bytes()));
+
+        match (
+            HeaderValue::from_str(&timestamp),
+            HeaderValue::from_str(&signature),
+        ) {
+            (Ok(timestamp), Ok(signature)) => {
+                headers.insert(self.timestamp_header.clone(), timestamp);
+                headers.insert(self.signature_header.clone(), signature);
+            }
+            _ => (),
+        }
+    }
+}
+
+impl std::fmt::Debug for HmacSigner {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.debug_struct("HmacSigner")
+            .field("signature_header", &self.signature_header)
+            .field("timestamp_header", &self.timestamp_header)
+            .finish_non_exhaustive()
+    }
+}
+
+impl CrawlMiddleware for HmacSigner {
+    fn before_send(&self, request: &mut Request) {
+        let mut headers = HeaderMap::new();
+
+        self.sign(
+            request.method().as_str(),
+            request.url().as_str(),
+            request
+                .body()
+                .and_then(|body| body.as_bytes())
+                .unwrap_or_default(),
+            SystemTime::now(),
+            &mut headers,
+        );
+        request.headers_mut().extend(headers);
+    }
+}
+
+/// The signer adding the AWS Signature Version 4 of the requests with the credentials of the
+/// region and the service like `s3`. The host, the `x-amz-date`, the `x-amz-content-sha256`, and the
+/// `x-amz-security-token` of the temporary credentials are signed.
+#[derive(Clone)]
+pub struct SigV4Signer {
+    /// The access key id of the credentials.
+    pub access_key: String,
+    /// The secret access key of the credentials.
+    pub secret_key: String,
+    /// The session token of the temporary credentials. Defaults to None.
+    pub session_token: Option<String>,
+    /// The region of the service like `us-east-1`.
+    pub region: String,
+    /// The service like `s3` or `execute-api`.
+    pub service: String,
+}
+
+impl SigV4Signer {
+    /// Establish the signer with the credentials of the region and the service.
+    pub fn new(access_key: &str, secret_key: &str, region: &str, service: &str) -> Self {
+        Self {
+            access_key: access_key.into(),
+            secret_key: secret_key.into(),
+            session_token: None,
+            region: region.into(),
+            service: service.into(),
+        }
+    }
+
+    /// Sign the requests with the session token of the temporary credentials.
+    pub fn with_session_token(&mut self, session_token: Option<&str>) -> &mut Self {
+        self.session_token = session_token.map(|token| token.into());
+        self
+    }
+
+    /// Sign the request of the method to the url with the body sent at the time adding the
+    /// authorization and the signed `x-amz-*` headers to the headers.
+    pub fn sign(
+        &self,
+        method: &str,
+        url: &str,
+        body: &[u8],
+        time: SystemTime,
+        headers: &mut HeaderMap,
+    ) {
+        let url = match Url::parse(url) {
+            Ok(url) => url,
+            _ => return,
+        };
+        let host = match (url.host_str(), url.port()) {
+            (Some(host), Some(port)) => format!("{}:{}", host, port),
+            (Some(host), _) => host.to_string(),
+            _ => return,
+        };
+        // the date of the ISO 8601 time in the basic format like 20130524T000000Z
+        let amz_date = format!("{}Z", get_iso_date(time)[..19].replace(&['-', ':'][..], ""));
+        let date = &amz_date[..8];
+        let content_sha256 = get_hex(&Sha256::digest(body));
+
+        let mut signed_headers = vec![
+            ("host", host),
+            ("x-amz-content-sha256", content_sha256.clone()),
+            ("x-amz-date", amz_date.clone()),
+        ];
+
+        match &self.session_token {
+            Some(session_token) => {
+                signed_headers.push(("x-amz-security-token", session_token.clone()))
+            }
+            _ => (),
+        }
+
+        let canonical_headers: String = signed_headers
+            .iter()
+            .map(|(name, value)| format!("{}:{}\n", name, value.trim()))
+            .collect();
+        let signed_header_names = signed_headers
+            .iter()
+            .map(|(name, _)| *name)
+            .collect::<Vec<_>>()
+            .join(";");
+        let canonical_request = format!(
+            "{}\n{}\n{}\n{}\n{}\n{}",
+            method,
+            get_canonical_uri(url.path(), self.service != "s3"),
+            get_canonical_query(&url),
+            canonical_headers,
+            signed_header_names,
+            content_sha256
+        );
+        let scope = format!("{}/{}/{}/aws4_request", date, self.region, self.service);
+        let string_to_sign = format!(
+            "{}\n{}\n{}\n{}",
+            SIGV4_ALGORITHM,
+            amz_date,
+            scope,
+            get_hex(&Sha256::digest(canonical_request.as_bytes()))
+        );
+
+        let mut key = get_hmac(
+            format!("AWS4{}", self.secret_key).as_bytes(),
+            date.as_bytes(),
+        );
+
+        for part in [self.region.as_str(), self.service.as_str(), "aws4_request"] {
+            key = get_hmac(&key, part.as_bytes());
+        }
+
+        let authorization = format!(
+            "{} Credential={}/{}, SignedHeaders={}, Signature={}",
+            SIGV4_ALGORITHM,
+            self.access_key,
+            scope,
+            signed_header_names,
+            get_hex(&get_hmac(&key, string_to_sign.as_bytes()))
+        );
+
+        match HeaderValue::from_str(&authorization) {
+            Ok(mut authorization) => {
+                authorization.set_sensitive(true);
+                headers.insert(AUTHORIZATION, authorization);
+            }
+            _ => return,
+        }
+
+        // the host is set by the client from the url
+        for (name, value) in signed_headers.into_iter().skip(1) {
+            match HeaderValue::from_str(&value) {
+                Ok(value) => {
+                    headers.insert(name, value);
+                }
+                _ => (),
+            }
+        }
+    }
+}
+
+impl std::fmt::Debug for SigV4Signer {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.debug_struct("SigV4Signer")
+            .field("access_key", &self.access_key)
+            .field("region", &self.region)
+            .field("service", &self.service)
+            .finish_non_exhaustive()
+    }
+}
+
+impl CrawlMiddleware for SigV4Signer {
+    fn before_send(&self, request: &mut Request) {
+        let mut headers = HeaderMap::new();
+
+        self.sign(
+            request.method().as_str(),
+            request.url().as_str(),
+            request
+                .body()
+                .and_then(|body| body.as_bytes())
+                .unwrap_or_default(),
+            SystemTime::now(),
+            &mut headers,
+        );
+        request.headers_mut().extend(headers);
+    }
+}
+
+/// Get the HMAC-SHA256 of the message keyed by the key.
+fn get_hmac(key: &[u8], message: &[u8]) -> [u8; 32] {
+    match Hmac::<Sha256>::new_from_slice(key) {
+        Ok(mut mac) => {
+            mac.update(message);
+            mac.finalize().into_bytes().into()
+        }
+        _ => Default::default(),
+    }
+}
+
+/// Get the bytes in lowercase hex.
+fn get_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+/// Get the canonical path of the AWS signatures with the segments encoded once for S3 and twice
+/// for the other services.
+fn get_canonical_uri(path: &str, double_encode: bool) -> String {
+    let uri = path
+        .split('/')
+        .map(|segment| {
+            let segment = percent_decode_str(segment).decode_utf8_lossy();
+            let encoded = utf8_percent_encode(&segment, AWS_ENCODE_SET).to_string();
+
+            if double_encode {
+                utf8_percent_encode(&encoded, AWS_ENCODE_SET).to_string()
+            } else {
+                encoded
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("/");
+
+    if uri.is_empty() {
+        "/".into()
+    } else {
+        uri
+    }
+}
+
+/// Get the canonical query of the AWS signatures with the parameters encoded and sorted.
+fn get_canonical_query(url: &Url) -> String {
+    let mut params: Vec<(String, String)> = url
+        .query_pairs()
+        .map(|(name, value)| {
+            (
+                utf8_percent_encode(&name, AWS_ENCODE_SET).to_string(),
+                utf8_percent_encode(&value, AWS_ENCODE_SET).to_string(),
+            )
+        })
+        .collect();
+
+    params.sort();
+
+    params
+        .iter()
+        .map(|(name, value)| format!("{}={}", name, value))
+        .collect::<Vec<_>>()
+        .join("&")
+}
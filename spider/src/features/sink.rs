@@ -0,0 +1,282 @@
+use crate::page::Page;
+#[cfg(any(feature = "kafka", feature = "nats", feature = "s3"))]
+use crate::utils::log;
+use std::sync::Mutex;
+
+/// The output of the pages of a crawl.
+pub trait PageSink: std::fmt::Debug + Send + Sync {
+    /// Publish the JSON record of the page keyed by its url.
+    fn publish(&self, url: &str, record: &[u8]);
+    /// Include the body of the pages in the records. Defaults to true.
+    fn include_body(&self) -> bool {
+        true
+    }
+    /// Publish the body of the page apart from its record like to an object storage. Defaults to
+    /// skip the body.
+    fn publish_body(&self, _page: &Page) {}
+    /// Flush the records published when the crawl ends.
+    fn flush(&self) {}
+}
+
+/// The records of the pages kept in memory.
+#[derive(Debug, Default)]
+pub struct MemorySink {
+    /// The url and the record of the pages published.
+    records: Mutex<Vec<(String, Vec<u8>)>>,
+}
+
+impl MemorySink {
+    /// Establish a new empty sink in memory.
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Get the url and the record of the pages published in the order published.
+    pub fn get_records(&self) -> Vec<(String, Vec<u8>)> {
+        match self.records.lock() {
+            Ok(records) => records.clone(),
+            _ => Default::default(),
+        }
+    }
+}
+
+impl PageSink for MemorySink {
+    fn publish(&self, url: &str, record: &[u8]) {
+        match self.records.lock() {
+            Ok(mut records) => records.push((url.into(), record.to_vec())),
+            _ => (),
+        }
+    }
+}
+
+#[cfg(feature = "kafka")]
+/// The records of the pages produced to a Kafka topic keyed by their url.
+pub struct KafkaSink {
+    /// The producer sending the records queued in the background.
+    producer: rdkafka::producer::ThreadedProducer<rdkafka::producer::DefaultProducerContext>,
+    /// The topic of the records.
+    topic: String,
+    /// Include the body of the pages in the records.
+    body: bool,
+}
+
+#[cfg(feature = "kafka")]
+impl std::fmt::Debug for KafkaSink {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("KafkaSink")
+            .field("topic", &self.topic)
+            .field("body", &self.body)
+            .finish()
+    }
+}
+
+#[cfg(feature = "kafka")]
+impl KafkaSink {
+    /// Establish the producer of the topic on the brokers like `127.0.0.1:9092`. The records over
+    /// the `message.max.bytes` of the brokers are rejected, exclude the body of the large pages.
+    pub fn new(brokers: &str, topic: &str, body: bool) -> rdkafka::error::KafkaResult<Self> {
+        Ok(Self {
+            producer: rdkafka::ClientConfig::new()
+                .set("bootstrap.servers", brokers)
+                .set("message.timeout.ms", "30000")
+                .create()?,
+            topic: topic.into(),
+            body,
+        })
+    }
+}
+
+#[cfg(feature = "kafka")]
+impl PageSink for KafkaSink {
+    fn publish(&self, url: &str, record: &[u8]) {
+        use rdkafka::producer::{BaseRecord, Producer};
+
+        let mut message = BaseRecord::to(&self.topic).key(url).payload(record);
+
+        // wait for the queue of the producer to drain when full
+        loop {
+            match self.producer.send(message) {
+                Ok(_) => break,
+                Err((
+                    rdkafka::error::KafkaError::MessageProduction(
+                        rdkafka::types::RDKafkaErrorCode::QueueFull,
+                    ),
+                    m,
+                )) => {
+                    message = m;
+                    let _ = self.producer.flush(std::time::Duration::from_millis(100));
+                }
+                Err((e, _)) => {
+                    log("kafka publish failed - ", e.to_string());
+                    break;
+                }
+            }
+        }
+    }
+
+    fn include_body(&self) -> bool {
+        self.body
+    }
+
+    fn flush(&self) {
+        use rdkafka::producer::Producer;
+
+        match self.producer.flush(std::time::Duration::from_secs(30)) {
+            Err(e) => log("kafka flush failed - ", e.to_string()),
+            _ => (),
+        }
+    }
+}
+
+#[cfg(feature = "nats")]
+/// The records of the pages published to a NATS subject with their url as the header `Page-Url`.
+#[derive(Debug)]
+pub struct NatsSink {
+    /// The connection to the server.
+    connection: nats::Connection,
+    /// The subject of the records.
+    subject: String,
+    /// Include the body of the pages in the records.
+    body: bool,
+}
+
+#[cfg(feature = "nats")]
+impl NatsSink {
+    /// Connect to the server of the url like `nats://127.0.0.1:4222` to publish to the subject. The
+    /// records over the `max_payload` of the server are rejected, exclude the body of the large
+    /// pages.
+    pub fn new(url: &str, subject: &str, body: bool) -> std::io::Result<Self> {
+        Ok(Self {
+            connection: nats::connect(url)?,
+            subject: subject.into(),
+            body,
+        })
+    }
+}
+
+#[cfg(feature = "nats")]
+impl PageSink for NatsSink {
+    fn publish(&self, url: &str, record: &[u8]) {
+        let mut headers = nats::HeaderMap::new();
+
+        headers.insert("Page-Url", url);
+
+        match self.connection.publish_with_reply_or_headers(
+            &self.subject,
+            None,
+            Some(&headers),
+            record,
+        ) {
+            Err(e) => log("nats publish failed - ", e.to_string()),
+            _ => (),
+        }
+    }
+
+    fn include_body(&self) -> bool {
+        self.body
+    }
+
+    fn flush(&self) {
+        match self.connection.flush() {
+            Err(e) => log("nats flush failed - ", e.to_string()),
+            _ => (),
+        }
+    }
+}
+
+/// Get the key of the object of the url as the prefix with the SHA-256 of the url in hex.
+#[cfg(feature = "s3")]
+pub fn get_object_key(prefix: &str, url: &str) -> String {
+    use sha2::{Digest, Sha256};
+
+    let mut key = String::from(prefix);
+
+    for byte in Sha256::digest(url.as_bytes()) {
+        key.push_str(&format!("{:02x}", byte));
+    }
+
+    key
+}
+
+#[cfg(feature = "s3")]
+/// The bodies of the pages stored as the objects of an S3 or MinIO bucket keyed by the SHA-256 of
+/// their url with the record of the page as the metadata object of the key with `.json`.
+pub struct S3Sink {
+    /// The bucket of the objects.
+    bucket: s3::Bucket,
+    /// The prefix of the keys of the objects like `crawls/2024/`.
+    prefix: String,
+}
+
+#[cfg(feature = "s3")]
+impl std::fmt::Debug for S3Sink {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("S3Sink")
+            .field("bucket", &self.bucket.name)
+            .field("prefix", &self.prefix)
+            .finish()
+    }
+}
+
+#[cfg(feature = "s3")]
+impl S3Sink {
+    /// Establish the bucket of the region with the credentials of the environment or the profile.
+    /// Use a `Region::Custom` with the endpoint of a MinIO server like `http://127.0.0.1:9000` to
+    /// address the bucket by its path.
+    pub fn new(bucket: &str, region: s3::Region, prefix: &str) -> Result<Self, s3::error::S3Error> {
+        let path_style = matches!(region, s3::Region::Custom { .. });
+        let bucket = s3::Bucket::new(bucket, region, s3::creds::Credentials::default()?)?;
+
+        Ok(Self {
+            bucket: if path_style {
+                bucket.with_path_style()
+            } else {
+                bucket
+            },
+            prefix: prefix.into(),
+        })
+    }
+
+    /// Put the object of the key logging the failures.
+    fn put(&self, key: &str, content: &[u8], content_type: &str) {
+        match self
+            .bucket
+            .put_object_with_content_type(key, content, content_type)
+        {
+            Ok(response) if response.status_code() >= 300 => log(
+                "s3 put failed - ",
+                format!("{} {}", response.status_code(), key),
+            ),
+            Err(e) => log("s3 put failed - ", e.to_string()),
+            _ => (),
+        }
+    }
+}
+
+#[cfg(feature = "s3")]
+impl PageSink for S3Sink {
+    fn publish(&self, url: &str, record: &[u8]) {
+        let key = get_object_key(&self.prefix, url);
+
+        self.put(&format!("{}.json", key), record, "application/json");
+    }
+
+    fn include_body(&self) -> bool {
+        false
+    }
+
+    fn publish_body(&self, page: &Page) {
+        let content_type = page
+            .headers
+            .as_ref()
+            .and_then(|headers| headers.get(reqwest::header::CONTENT_TYPE))
+            .and_then(|content_type| content_type.to_str().ok())
+            .unwrap_or("application/octet-stream");
+
+        self.put(
+            &get_object_key(&self.prefix, page.get_url()),
+            page.get_html_bytes_u8(),
+            content_type,
+        );
+    }
+}
@@ -0,0 +1,181 @@
+use crate::page::Page;
+use crate::utils::{get_iso_date, log};
+use crate::website::UrlResult;
+use crate::CaseInsensitiveString;
+use hashbrown::HashSet;
+use rusqlite::{params, Connection};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::Duration;
+
+/// The schema of the database created when missing.
+const SCHEMA: &str = "CREATE TABLE IF NOT EXISTS pages (
+    url TEXT PRIMARY KEY,
+    final_url TEXT,
+    status_code INTEGER NOT NULL,
+    content_type TEXT,
+    headers TEXT,
+    body BLOB,
+    depth INTEGER,
+    retries INTEGER,
+    error_kind TEXT,
+    elapsed_ms INTEGER,
+    crawled_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS links (
+    source TEXT NOT NULL,
+    target TEXT NOT NULL,
+    PRIMARY KEY (source, target)
+);";
+
+/// The database of the pages and the links of the crawls.
+#[derive(Debug)]
+pub struct SqliteStore {
+    /// The path of the database.
+    path: PathBuf,
+    /// Write the body of the pages.
+    body: bool,
+    /// The connection writing to the database.
+    connection: Mutex<Connection>,
+}
+
+impl SqliteStore {
+    /// Open the database at the path creating its directory and its schema when missing. The body
+    /// of the pages is written when included.
+    pub fn new<P: AsRef<Path>>(path: P, body: bool) -> rusqlite::Result<Self> {
+        let path = path.as_ref();
+
+        match path.parent() {
+            Some(dir) if !dir.as_os_str().is_empty() => {
+                let _ = std::fs::create_dir_all(dir);
+            }
+            _ => (),
+        }
+
+        let connection = Connection::open(path)?;
+
+        connection.pragma_update(None, "journal_mode", "WAL")?;
+        connection.pragma_update(None, "synchronous", "NORMAL")?;
+        connection.busy_timeout(Duration::from_secs(5))?;
+        connection.execute_batch(SCHEMA)?;
+
+        Ok(Self {
+            path: path.into(),
+            body,
+            connection: Mutex::new(connection),
+        })
+    }
+
+    /// Get the path of the database.
+    pub fn get_path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Write the page crawled with the links found on it.
+    pub fn insert_page(&self, page: &Page, links: &HashSet<CaseInsensitiveString>) {
+        let headers = page.headers.as_ref();
+        let content_type = headers
+            .and_then(|headers| headers.get(reqwest::header::CONTENT_TYPE))
+            .and_then(|content_type| content_type.to_str().ok());
+        let headers = headers.map(|headers| {
+            headers
+                .iter()
+                .map(|(name, value)| {
+                    format!("{}: {}", name, String::from_utf8_lossy(value.as_bytes()))
+                })
+                .collect::<Vec<String>>()
+                .join("\n")
+        });
+        let body = if self.body {
+            Some(page.get_html_bytes_u8())
+        } else {
+            None
+        };
+
+        let result = match self.connection.lock() {
+            Ok(mut connection) => (|| {
+                let transaction = connection.transaction()?;
+
+                transaction.execute(
+                    "INSERT INTO pages (url, final_url, status_code, content_type, headers, body, crawled_at)
+                    VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)
+                    ON CONFLICT(url) DO UPDATE SET final_url = excluded.final_url,
+                    status_code = excluded.status_code, content_type = excluded.content_type,
+                    headers = excluded.headers, body = excluded.body, crawled_at = excluded.crawled_at",
+                    params![
+                        page.get_url(),
+                        page.final_redirect_destination,
+                        page.status_code.as_u16(),
+                        content_type,
+                        headers,
+                        body,
+                        get_iso_date(std::time::SystemTime::now()),
+                    ],
+                )?;
+
+                {
+                    let mut statement = transaction.prepare_cached(
+                        "INSERT OR IGNORE INTO links (source, target) VALUES (?1, ?2)",
+                    )?;
+
+                    for link in links {
+                        statement.execute(params![page.get_url(), link.inner().as_str()])?;
+                    }
+                }
+
+                transaction.commit()
+            })(),
+            _ => Ok(()),
+        };
+
+        match result {
+            Err(e) => log("sqlite insert failed - ", e.to_string()),
+            _ => (),
+        }
+    }
+
+    /// Write the depth, the retries, the error, and the duration of the request of the url.
+    pub fn insert_result(&self, url: &str, result: &UrlResult, elapsed: Duration) {
+        let result = match self.connection.lock() {
+            Ok(connection) => connection
+                .execute(
+                    "INSERT INTO pages (url, status_code, depth, retries, error_kind, elapsed_ms, crawled_at)
+                    VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)
+                    ON CONFLICT(url) DO UPDATE SET depth = excluded.depth,
+                    retries = excluded.retries, error_kind = excluded.error_kind,
+                    elapsed_ms = excluded.elapsed_ms",
+                    params![
+                        url,
+                        result.status_code.as_u16(),
+                        result.depth as i64,
+                        result.retries,
+                        result.error_kind.map(|error_kind| format!("{:?}", error_kind)),
+                        elapsed.as_millis() as i64,
+                        get_iso_date(std::time::SystemTime::now()),
+                    ],
+                )
+                .map(|_| ()),
+            _ => Ok(()),
+        };
+
+        match result {
+            Err(e) => log("sqlite insert failed - ", e.to_string()),
+            _ => (),
+        }
+    }
+
+    /// Get the count of the pages written.
+    pub fn len(&self) -> usize {
+        match self.connection.lock() {
+            Ok(connection) => connection
+                .query_row("SELECT COUNT(*) FROM pages", [], |row| row.get::<_, i64>(0))
+                .map_or(0, |count| count as usize),
+            _ => 0,
+        }
+    }
+
+    /// No pages are written.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
@@ -0,0 +1,189 @@
+use crate::utils::RequestErrorKind;
+use crate::website::UrlResult;
+use hashbrown::HashMap;
+use std::time::{Duration, Instant};
+
+/// The distribution of the durations of the requests.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct LatencyStats {
+    /// The average duration of the requests.
+    pub average: Duration,
+    /// The median duration of the requests.
+    pub p50: Duration,
+    /// The duration of the requests at the 90th percentile.
+    pub p90: Duration,
+    /// The duration of the requests at the 99th percentile.
+    pub p99: Duration,
+    /// The longest duration of the requests.
+    pub max: Duration,
+}
+
+impl LatencyStats {
+    /// Get the distribution of the durations of the requests.
+    fn new(latencies: &mut [Duration]) -> Self {
+        if latencies.is_empty() {
+            return Default::default();
+        }
+
+        latencies.sort_unstable();
+
+        let total: Duration = latencies.iter().sum();
+        // the nearest rank of the percentile
+        let percentile =
+            |percentile: usize| latencies[((latencies.len() * percentile + 99) / 100).max(1) - 1];
+
+        Self {
+            average: total / latencies.len() as u32,
+            p50: percentile(50),
+            p90: percentile(90),
+            p99: percentile(99),
+            max: latencies[latencies.len() - 1],
+        }
+    }
+}
+
+/// The totals of the requests of a host of the crawl.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct HostStats {
+    /// The pages fetched from the host.
+    pub pages: u64,
+    /// The requests of the host failed.
+    pub failures: u64,
+    /// The bytes of the responses of the host.
+    pub bytes: u64,
+    /// The distribution of the durations of the requests of the host.
+    pub latency: LatencyStats,
+}
+
+/// The summary of the requests of a crawl.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct CrawlStats {
+    /// The pages fetched including the failures.
+    pub pages: u64,
+    /// The requests failed from a network or server error or a bot challenge not solved.
+    pub failures: u64,
+    /// The requests failed without a response per class of the error.
+    pub errors: HashMap<RequestErrorKind, u64>,
+    /// The responses per status code.
+    pub status_codes: HashMap<u16, u64>,
+    /// The pages with a bot challenge not solved.
+    pub challenges: u64,
+    /// The bytes of the responses.
+    pub bytes: u64,
+    /// The duration from the first request to the last request handled.
+    pub duration: Duration,
+    /// The distribution of the durations of the requests.
+    pub latency: LatencyStats,
+    /// The pages fetched per second since the first request handled.
+    pub pages_per_second: Vec<u64>,
+    /// The totals of the requests per host.
+    pub hosts: HashMap<String, HostStats>,
+}
+
+impl CrawlStats {
+    /// Get the average of the pages fetched per second over the duration of the crawl.
+    pub fn get_average_pages_per_second(&self) -> f64 {
+        let seconds = self.duration.as_secs_f64();
+
+        if seconds > 0.0 {
+            self.pages as f64 / seconds
+        } else {
+            self.pages as f64
+        }
+    }
+}
+
+/// The recorder of the requests of a crawl.
+#[derive(Debug, Default, Clone)]
+pub(crate) struct StatsRecorder {
+    /// The time the first request was handled.
+    started: Option<Instant>,
+    /// The totals of the requests recorded without the latencies.
+    stats: CrawlStats,
+    /// The durations of the requests per host.
+    latencies: HashMap<String, Vec<Duration>>,
+}
+
+impl StatsRecorder {
+    /// Record the request of the host with its result, its duration, and the bytes of its response.
+    pub(crate) fn record(
+        &mut self,
+        host: Option<&str>,
+        result: &UrlResult,
+        elapsed: Duration,
+        bytes: usize,
+    ) {
+        let now = Instant::now();
+        let started = *self.started.get_or_insert(now);
+        let second = now.duration_since(started).as_secs() as usize;
+        let failed = result.is_failed();
+        let stats = &mut self.stats;
+
+        stats.pages += 1;
+        stats.bytes += bytes as u64;
+        stats.duration = now.duration_since(started);
+
+        if failed {
+            stats.failures += 1;
+        }
+
+        match result.error_kind {
+            Some(error_kind) => *stats.errors.entry(error_kind).or_default() += 1,
+            _ => {
+                *stats
+                    .status_codes
+                    .entry(result.status_code.as_u16())
+                    .or_default() += 1
+            }
+        }
+
+        if result.challenge.is_some() {
+            stats.challenges += 1;
+        }
+
+        if stats.pages_per_second.len() <= second {
+            stats.pages_per_second.resize(second + 1, 0);
+        }
+        stats.pages_per_second[second] += 1;
+
+        let host = host.unwrap_or_default();
+        let host_stats = stats.hosts.entry_ref(host).or_default();
+
+        host_stats.pages += 1;
+        host_stats.bytes += bytes as u64;
+
+        if failed {
+            host_stats.failures += 1;
+        }
+
+        self.latencies.entry_ref(host).or_default().push(elapsed);
+    }
+
+    /// Get the summary of the requests recorded.
+    pub(crate) fn get_stats(&self) -> CrawlStats {
+        let mut stats = self.stats.clone();
+        let mut latencies = Vec::with_capacity(stats.pages as usize);
+
+        for (host, host_latencies) in self.latencies.iter() {
+            let mut host_latencies = host_latencies.clone();
+
+            match stats.hosts.get_mut(host) {
+                Some(host_stats) => host_stats.latency = LatencyStats::new(&mut host_latencies),
+                _ => (),
+            }
+
+            latencies.extend(host_latencies);
+        }
+
+        stats.latency = LatencyStats::new(&mut latencies);
+
+        stats
+    }
+
+    /// Clear the requests recorded.
+    pub(crate) fn clear(&mut self) {
+        self.started = None;
+        self.stats = Default::default();
+        self.latencies.clear();
+    }
+}
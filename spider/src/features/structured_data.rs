@@ -0,0 +1,156 @@
+use crate::packages::scraper::{ElementRef, Html, Selector};
+use crate::page::convert_abs_path;
+use serde_json::{Map, Value};
+use url::Url;
+
+/// The structured data of a page.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct StructuredData {
+    /// The `application/ld+json` blocks parsed. The invalid blocks are skipped.
+    pub json_ld: Vec<Value>,
+    /// The top level microdata items.
+    pub microdata: Vec<Value>,
+    /// The top level RDFa items.
+    pub rdfa: Vec<Value>,
+}
+
+impl StructuredData {
+    /// Get the structured data of the html parsed resolving the urls from the base.
+    pub fn new(html: &Html, base: &Url) -> Self {
+        let mut json_ld = Vec::new();
+
+        match Selector::parse(r#"script[type="application/ld+json"]"#) {
+            Ok(selector) => {
+                for element in html.select(&selector) {
+                    match serde_json::from_str(&element.text().collect::<String>()) {
+                        Ok(value) => json_ld.push(value),
+                        _ => (),
+                    }
+                }
+            }
+            _ => (),
+        }
+
+        Self {
+            json_ld,
+            microdata: get_items(html, base, &MICRODATA),
+            rdfa: get_items(html, base, &RDFA),
+        }
+    }
+}
+
+/// The attributes of the items of a vocabulary.
+struct Vocabulary {
+    /// The attribute starting an item.
+    scope: &'static str,
+    /// The attribute of the type of an item.
+    kind: &'static str,
+    /// The attribute of the names of a property.
+    property: &'static str,
+}
+
+/// The attributes of the microdata items.
+const MICRODATA: Vocabulary = Vocabulary {
+    scope: "itemscope",
+    kind: "itemtype",
+    property: "itemprop",
+};
+
+/// The attributes of the RDFa items.
+const RDFA: Vocabulary = Vocabulary {
+    scope: "typeof",
+    kind: "typeof",
+    property: "property",
+};
+
+/// Get the items of the vocabulary not the property of another item.
+fn get_items(html: &Html, base: &Url, vocabulary: &Vocabulary) -> Vec<Value> {
+    match Selector::parse(&format!("[{}]", vocabulary.scope)) {
+        Ok(selector) => html
+            .select(&selector)
+            .filter(|element| element.value().attr(vocabulary.property).is_none())
+            .map(|element| get_item(element, base, vocabulary))
+            .collect(),
+        _ => Default::default(),
+    }
+}
+
+/// Get the item of the element with its type and properties.
+fn get_item(element: ElementRef, base: &Url, vocabulary: &Vocabulary) -> Value {
+    let mut item = Map::new();
+
+    match element.value().attr(vocabulary.kind) {
+        Some(kind) if !kind.trim().is_empty() => {
+            item.insert("@type".into(), kind.trim().into());
+        }
+        _ => (),
+    }
+    insert_properties(element, base, vocabulary, &mut item);
+
+    Value::Object(item)
+}
+
+/// Insert the properties of the descendants of the element stopping at the nested items.
+fn insert_properties(
+    element: ElementRef,
+    base: &Url,
+    vocabulary: &Vocabulary,
+    item: &mut Map<String, Value>,
+) {
+    for child in element.children().filter_map(ElementRef::wrap) {
+        let nested = child.value().attr(vocabulary.scope).is_some();
+
+        match child.value().attr(vocabulary.property) {
+            Some(names) => {
+                let value = if nested {
+                    get_item(child, base, vocabulary)
+                } else {
+                    get_property_value(child, base)
+                };
+
+                for name in names.split_ascii_whitespace() {
+                    match item.get_mut(name) {
+                        Some(Value::Array(values)) => values.push(value.clone()),
+                        Some(previous) => {
+                            *previous = Value::Array(vec![previous.take(), value.clone()])
+                        }
+                        _ => {
+                            item.insert(name.into(), value.clone());
+                        }
+                    }
+                }
+            }
+            _ => (),
+        }
+
+        if !nested {
+            insert_properties(child, base, vocabulary, item);
+        }
+    }
+}
+
+/// Get the value of the property from the content, the url, the machine value, or the text of the
+/// element.
+fn get_property_value(element: ElementRef, base: &Url) -> Value {
+    let attr = |name: &str| element.attr(name).map(|value| value.trim());
+    let (url, value) = match element.value().name() {
+        "a" | "area" | "link" => (attr("href"), None),
+        "audio" | "embed" | "iframe" | "img" | "source" | "track" | "video" => (attr("src"), None),
+        "object" => (attr("data"), None),
+        "time" => (None, attr("datetime")),
+        "data" | "meter" => (None, attr("value")),
+        _ => (None, None),
+    };
+
+    match (attr("content"), url, value) {
+        (Some(content), _, _) => content.into(),
+        (_, Some(url), _) => convert_abs_path(base, url).as_str().into(),
+        (_, _, Some(value)) => value.into(),
+        _ => element
+            .text()
+            .flat_map(|text| text.split_whitespace())
+            .collect::<Vec<_>>()
+            .join(" ")
+            .into(),
+    }
+}
@@ -0,0 +1,61 @@
+use opentelemetry::trace::TraceError;
+use opentelemetry_otlp::WithExportConfig;
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::util::SubscriberInitExt;
+
+/// The guard flushing the spans batched and shutting down the export when dropped.
+#[derive(Debug)]
+pub struct TelemetryGuard {
+    /// The guard is only made by the export installed.
+    _private: (),
+}
+
+impl Drop for TelemetryGuard {
+    fn drop(&mut self) {
+        opentelemetry::global::shutdown_tracer_provider();
+    }
+}
+
+/// Get the layer exporting the spans with OTLP over gRPC to the collector of the endpoint like
+/// `http://127.0.0.1:4317` as the service of the name. The layer is added to a subscriber in the
+/// runtime of tokio.
+pub fn otlp_layer<S>(
+    endpoint: &str,
+    service_name: &str,
+) -> Result<
+    tracing_opentelemetry::OpenTelemetryLayer<S, opentelemetry_sdk::trace::Tracer>,
+    TraceError,
+>
+where
+    S: tracing::Subscriber + for<'span> tracing_subscriber::registry::LookupSpan<'span>,
+{
+    let tracer = opentelemetry_otlp::new_pipeline()
+        .tracing()
+        .with_exporter(
+            opentelemetry_otlp::new_exporter()
+                .tonic()
+                .with_endpoint(endpoint),
+        )
+        .with_trace_config(opentelemetry_sdk::trace::config().with_resource(
+            opentelemetry_sdk::Resource::new(vec![opentelemetry::KeyValue::new(
+                "service.name",
+                service_name.to_string(),
+            )]),
+        ))
+        .install_batch(opentelemetry_sdk::runtime::Tokio)?;
+
+    Ok(tracing_opentelemetry::layer().with_tracer(tracer))
+}
+
+/// Install the global subscriber exporting the spans of the crawls up to the debug level with OTLP
+/// over gRPC to the collector of the endpoint like `http://127.0.0.1:4317` as the service of the
+/// name. Keep the guard returned until the crawls end to flush the spans batched.
+pub fn init_otlp(endpoint: &str, service_name: &str) -> Result<TelemetryGuard, TraceError> {
+    tracing_subscriber::registry()
+        .with(tracing_subscriber::filter::LevelFilter::DEBUG)
+        .with(otlp_layer(endpoint, service_name)?)
+        .try_init()
+        .map_err(|e| TraceError::from(e.to_string()))?;
+
+    Ok(TelemetryGuard { _private: () })
+}
@@ -0,0 +1,288 @@
+use crate::packages::scraper::node::Element;
+use crate::packages::scraper::{ElementRef, Html, Node};
+use ego_tree::{NodeId, NodeRef};
+use hashbrown::HashMap;
+
+/// The elements of the boilerplate stripped with their content.
+const BOILERPLATE_TAGS: [&str; 16] = [
+    "nav", "header", "footer", "aside", "form", "script", "style", "noscript", "template",
+    "iframe", "svg", "button", "select", "menu", "dialog", "head",
+];
+
+/// The words of the class and the id of the boilerplate elements.
+const BOILERPLATE_MARKERS: [&str; 22] = [
+    "ad",
+    "ads",
+    "advert",
+    "advertisement",
+    "banner",
+    "breadcrumb",
+    "breadcrumbs",
+    "comment",
+    "comments",
+    "cookie",
+    "footer",
+    "menu",
+    "modal",
+    "nav",
+    "navbar",
+    "newsletter",
+    "popup",
+    "promo",
+    "related",
+    "share",
+    "sidebar",
+    "social",
+];
+
+/// The words of the class and the id of the main content elements.
+const CONTENT_MARKERS: [&str; 7] = [
+    "article", "body", "content", "entry", "main", "post", "story",
+];
+
+/// The elements of the blocks of text of the main content.
+const BLOCK_TAGS: [&str; 13] = [
+    "p",
+    "h1",
+    "h2",
+    "h3",
+    "h4",
+    "h5",
+    "h6",
+    "li",
+    "pre",
+    "blockquote",
+    "td",
+    "dt",
+    "dd",
+];
+
+/// The main content of a page.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Article {
+    /// The title of the main content from its first heading or the `<title>` without the name of
+    /// the site.
+    pub title: Option<String>,
+    /// The text of the main content with the blocks separated by a blank line.
+    pub text: String,
+}
+
+impl Article {
+    /// Get the main content of the html parsed.
+    pub fn new(html: &Html) -> Self {
+        let content = get_main_content(html);
+        let text = match content {
+            Some(content) => get_text(content),
+            _ => Default::default(),
+        };
+
+        Self {
+            title: get_title(html, content),
+            text,
+        }
+    }
+}
+
+/// The element or one of its ancestors is boilerplate.
+fn is_boilerplate(node: NodeRef<Node>) -> bool {
+    std::iter::once(node)
+        .chain(node.ancestors())
+        .filter_map(|node| node.value().as_element())
+        .any(is_boilerplate_element)
+}
+
+/// The element is boilerplate by its tag, its role, or the markers of its class and id.
+fn is_boilerplate_element(element: &Element) -> bool {
+    if BOILERPLATE_TAGS.contains(&element.name()) {
+        return true;
+    }
+
+    match element.attr("role") {
+        Some("navigation" | "banner" | "contentinfo" | "complementary" | "dialog") => return true,
+        _ => (),
+    }
+
+    // the words of the main content win over the words of the boilerplate like `post-share`
+    let words = get_words(element);
+
+    !words
+        .iter()
+        .any(|word| CONTENT_MARKERS.contains(&word.as_str()))
+        && words
+            .iter()
+            .any(|word| BOILERPLATE_MARKERS.contains(&word.as_str()))
+}
+
+/// Get the words of the class and the id of the element lowercase like `site` and `footer` of
+/// `site-footer`.
+fn get_words(element: &Element) -> Vec<String> {
+    [element.attr("class"), element.attr("id")]
+        .iter()
+        .flatten()
+        .flat_map(|names| names.split(|c: char| !c.is_ascii_alphanumeric()))
+        .filter(|word| !word.is_empty())
+        .map(|word| word.to_ascii_lowercase())
+        .collect()
+}
+
+/// Get the text of the node with the whitespace collapsed.
+fn get_collapsed_text(element: ElementRef) -> String {
+    element
+        .text()
+        .flat_map(|text| text.split_whitespace())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Get the element of the main content scoring the parents of the paragraphs by their text and
+/// their link density.
+fn get_main_content(html: &Html) -> Option<ElementRef> {
+    let mut scores: HashMap<NodeId, f64> = HashMap::new();
+
+    for node in html.tree.root().descendants() {
+        let paragraph = match ElementRef::wrap(node) {
+            Some(element)
+                if matches!(element.value().name(), "p" | "pre" | "td" | "blockquote") =>
+            {
+                element
+            }
+            _ => continue,
+        };
+
+        if is_boilerplate(node) {
+            continue;
+        }
+
+        let text = get_collapsed_text(paragraph);
+
+        if text.len() < 25 {
+            continue;
+        }
+
+        let score = 1.0 + text.matches(',').count() as f64 + (text.len() as f64 / 100.0).min(3.0);
+
+        match node.parent() {
+            Some(parent) => {
+                *scores.entry(parent.id()).or_default() += score;
+
+                match parent.parent() {
+                    Some(grandparent) => {
+                        *scores.entry(grandparent.id()).or_default() += score / 2.0
+                    }
+                    _ => (),
+                }
+            }
+            _ => (),
+        }
+    }
+
+    scores
+        .into_iter()
+        .filter_map(|(id, score)| {
+            let element = ElementRef::wrap(html.tree.get(id)?)?;
+            let bonus = match element.value().name() {
+                "article" | "main" => 25.0,
+                "div" | "section" => 5.0,
+                _ => 0.0,
+            };
+
+            Some((element, (score + bonus) * (1.0 - get_link_density(element))))
+        })
+        .max_by(|a, b| a.1.partial_cmp(&b.1).unwrap_or(std::cmp::Ordering::Equal))
+        .map(|(element, _)| element)
+        .or_else(|| {
+            html.tree
+                .root()
+                .descendants()
+                .filter_map(ElementRef::wrap)
+                .find(|element| element.value().name() == "body")
+        })
+}
+
+/// Get the ratio of the text of the links in the text of the element.
+fn get_link_density(element: ElementRef) -> f64 {
+    let text_len = get_collapsed_text(element).len();
+
+    if text_len == 0 {
+        return 0.0;
+    }
+
+    let link_len: usize = element
+        .descendants()
+        .filter_map(ElementRef::wrap)
+        .filter(|element| element.value().name() == "a")
+        .map(|element| get_collapsed_text(element).len())
+        .sum();
+
+    (link_len as f64 / text_len as f64).min(1.0)
+}
+
+/// Get the text of the blocks of the main content without the boilerplate.
+fn get_text(content: ElementRef) -> String {
+    let mut blocks: Vec<String> = Vec::new();
+
+    for node in content.descendants() {
+        match ElementRef::wrap(node) {
+            Some(element)
+                if BLOCK_TAGS.contains(&element.value().name())
+                    && !node
+                        .ancestors()
+                        .take_while(|ancestor| ancestor.id() != content.id())
+                        .filter_map(ElementRef::wrap)
+                        .any(|ancestor| BLOCK_TAGS.contains(&ancestor.value().name()))
+                    && !node
+                        .ancestors()
+                        .take_while(|ancestor| ancestor.id() != content.id())
+                        .chain(std::iter::once(node))
+                        .filter_map(|node| node.value().as_element())
+                        .any(is_boilerplate_element) =>
+            {
+                let text = get_collapsed_text(element);
+
+                if !text.is_empty() {
+                    blocks.push(text);
+                }
+            }
+            _ => (),
+        }
+    }
+
+    if blocks.is_empty() {
+        get_collapsed_text(content)
+    } else {
+        blocks.join("\n\n")
+    }
+}
+
+/// Get the title of the main content from its first heading or the `<title>` of the page without
+/// the name of the site after a separator.
+fn get_title(html: &Html, content: Option<ElementRef>) -> Option<String> {
+    let heading = content.and_then(|content| {
+        content
+            .descendants()
+            .filter_map(ElementRef::wrap)
+            .find(|element| matches!(element.value().name(), "h1" | "h2"))
+            .map(get_collapsed_text)
+            .filter(|heading| !heading.is_empty())
+    });
+
+    match heading {
+        Some(heading) => Some(heading),
+        _ => html
+            .tree
+            .root()
+            .descendants()
+            .filter_map(ElementRef::wrap)
+            .find(|element| element.value().name() == "title")
+            .map(get_collapsed_text)
+            .map(|title| {
+                [" | ", " - ", " — ", " – ", " :: "]
+                    .iter()
+                    .find_map(|separator| title.split_once(separator).map(|(title, _)| title))
+                    .unwrap_or(&title)
+                    .trim()
+                    .to_string()
+            })
+            .filter(|title| !title.is_empty()),
+    }
+}
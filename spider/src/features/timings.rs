@@ -0,0 +1,76 @@
+use reqwest::dns::{Addrs, Name, Resolve, Resolving};
+use std::cell::Cell;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+tokio::task_local! {
+    /// The duration of the DNS lookup of the request of the task.
+    static DNS_DURATION: Cell<Option<Duration>>;
+}
+
+/// The durations of the phases of a request.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct RequestTimings {
+    /// The duration of the DNS lookup of the host. None when the lookups are not timed, the request
+    /// reused a connection, or the host was resolved in the background.
+    pub dns: Option<Duration>,
+    /// The duration from sending the request to the headers of the response including the DNS
+    /// lookup, the connect, and the TLS handshake of a new connection.
+    pub ttfb: Duration,
+    /// The duration of the download of the body of the response.
+    pub download: Duration,
+}
+
+impl RequestTimings {
+    /// Get the duration of the request from sending it to the end of the download of the body.
+    pub fn get_total(&self) -> Duration {
+        self.ttfb + self.download
+    }
+}
+
+/// Run the request recording the duration of the DNS lookup of the resolver of the client.
+pub(crate) async fn with_dns_duration<F: std::future::Future>(
+    future: F,
+) -> (F::Output, Option<Duration>) {
+    DNS_DURATION
+        .scope(Cell::new(None), async move {
+            let output = future.await;
+
+            (output, DNS_DURATION.with(|dns| dns.get()))
+        })
+        .await
+}
+
+/// The resolver of the clients of the crawl timing the lookups of the resolver wrapped or the
+/// system resolver.
+pub(crate) struct TimedResolver(pub Option<Arc<dyn Resolve>>);
+
+impl Resolve for TimedResolver {
+    fn resolve(&self, name: Name) -> Resolving {
+        let start = Instant::now();
+        let resolving = match &self.0 {
+            Some(resolver) => resolver.resolve(name),
+            _ => Box::pin(async move {
+                let addrs: Vec<SocketAddr> =
+                    crate::features::dns_cache::lookup_system(name.as_str())
+                        .await?
+                        .into_iter()
+                        .map(|ip| SocketAddr::new(ip, 0))
+                        .collect();
+                let addrs: Addrs = Box::new(addrs.into_iter());
+
+                Ok(addrs)
+            }),
+        };
+
+        Box::pin(async move {
+            let addrs = resolving.await;
+
+            // the lookups polled outside of the request like the prefetches are not recorded
+            let _ = DNS_DURATION.try_with(|dns| dns.set(Some(start.elapsed())));
+
+            addrs
+        })
+    }
+}
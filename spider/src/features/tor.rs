@@ -0,0 +1,140 @@
+use crate::configuration::TorConfig;
+use crate::utils::log;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::Arc;
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::net::TcpStream;
+
+/// The controller of the circuits of Tor shared across the tasks of the crawl.
+#[derive(Debug, Default)]
+pub struct TorControl {
+    /// The Tor daemon.
+    config: TorConfig,
+    /// The requests sent through Tor.
+    requests: AtomicUsize,
+    /// The new circuits requested.
+    circuits: AtomicUsize,
+    /// A new circuit is being requested.
+    rotating: AtomicBool,
+}
+
+impl TorControl {
+    /// Establish a new controller of the Tor daemon.
+    pub fn new(config: TorConfig) -> Self {
+        Self {
+            config,
+            ..Default::default()
+        }
+    }
+
+    /// Get the Tor daemon.
+    pub fn get_config(&self) -> &TorConfig {
+        &self.config
+    }
+
+    /// Get the count of the new circuits requested.
+    pub fn get_circuits(&self) -> usize {
+        self.circuits.load(Ordering::Relaxed)
+    }
+
+    /// The request should use a new circuit after the count of requests or the block of the
+    /// request.
+    pub fn should_rotate(&self, blocked: bool) -> bool {
+        let requests = self.requests.fetch_add(1, Ordering::Relaxed) + 1;
+
+        (blocked && self.config.rotate_on_block)
+            || (self.config.rotate_every > 0 && requests % self.config.rotate_every == 0)
+    }
+
+    /// Record a request sent through Tor requesting a new circuit in the background when it should
+    /// rotate. The requests wait for a single new circuit at a time.
+    pub fn record(self: &Arc<Self>, blocked: bool) {
+        if self.should_rotate(blocked) && !self.rotating.swap(true, Ordering::AcqRel) {
+            let tor = self.clone();
+
+            tokio::spawn(async move {
+                match tor.new_circuit().await {
+                    Ok(_) => {
+                        tor.circuits.fetch_add(1, Ordering::Relaxed);
+                    }
+                    Err(e) => log("tor new circuit failed - ", e.to_string()),
+                }
+                tor.rotating.store(false, Ordering::Release);
+            });
+        }
+    }
+
+    /// Request a new circuit for the next streams with the control port.
+    pub async fn new_circuit(&self) -> std::io::Result<()> {
+        let stream = TcpStream::connect(&self.config.control_addr).await?;
+        let mut stream = BufReader::new(stream);
+        let authenticate = match &self.config.control_password {
+            Some(password) => format!(
+                "AUTHENTICATE \"{}\"\r\n",
+                password.replace('\\', "\\\\").replace('"', "\\\"")
+            ),
+            _ => "AUTHENTICATE\r\n".into(),
+        };
+
+        stream.write_all(authenticate.as_bytes()).await?;
+        read_reply(&mut stream).await?;
+        stream.write_all(b"SIGNAL NEWNYM\r\n").await?;
+        read_reply(&mut stream).await?;
+        // the circuit is renewed whether the daemon closed the connection before the quit
+        stream.write_all(b"QUIT\r\n").await.unwrap_or_default();
+
+        Ok(())
+    }
+}
+
+/// Read the reply of a command of the control port failing when it is not `250 OK`.
+async fn read_reply(stream: &mut BufReader<TcpStream>) -> std::io::Result<()> {
+    let mut reply = String::new();
+    stream.read_line(&mut reply).await?;
+
+    if reply.starts_with("250") {
+        Ok(())
+    } else {
+        Err(std::io::Error::new(
+            std::io::ErrorKind::Other,
+            reply.trim().to_string(),
+        ))
+    }
+}
+
+#[tokio::test]
+async fn test_tor_control() {
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let control_addr = listener.local_addr().unwrap().to_string();
+
+    let server = tokio::spawn(async move {
+        let (stream, _) = listener.accept().await.unwrap();
+        let mut stream = BufReader::new(stream);
+        let mut commands = Vec::new();
+
+        for _ in 0..2 {
+            let mut command = String::new();
+            stream.read_line(&mut command).await.unwrap();
+            stream.write_all(b"250 OK\r\n").await.unwrap();
+            commands.push(command);
+        }
+
+        commands
+    });
+
+    let tor = TorControl::new(TorConfig {
+        control_addr,
+        control_password: Some("pass\"word".into()),
+        rotate_every: 2,
+        rotate_on_block: false,
+        ..Default::default()
+    });
+
+    assert!(!tor.should_rotate(true));
+    assert!(tor.should_rotate(false));
+    assert!(tor.new_circuit().await.is_ok());
+    assert_eq!(
+        server.await.unwrap(),
+        vec!["AUTHENTICATE \"pass\\\"word\"\r\n", "SIGNAL NEWNYM\r\n"]
+    );
+}
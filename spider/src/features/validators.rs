@@ -0,0 +1,197 @@
+use crate::CaseInsensitiveString;
+use hashbrown::{HashMap, HashSet};
+use reqwest::header::{HeaderMap, ETAG, LAST_MODIFIED};
+use std::io::Write;
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+/// The validators of a response with the links of the page to request the url conditionally.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Validators {
+    /// The `ETag` header of the response.
+    pub etag: Option<String>,
+    /// The `Last-Modified` header of the response.
+    pub last_modified: Option<String>,
+    /// The links found on the page to continue the crawl when unchanged.
+    pub links: HashSet<CaseInsensitiveString>,
+}
+
+impl Validators {
+    /// Get the validators from the headers of the response. Returns None without an `ETag` or a
+    /// `Last-Modified` header.
+    pub fn from_headers(headers: &HeaderMap) -> Option<Self> {
+        let etag = headers.get(ETAG).and_then(|v| v.to_str().ok());
+        let last_modified = headers.get(LAST_MODIFIED).and_then(|v| v.to_str().ok());
+
+        if etag.is_none() && last_modified.is_none() {
+            None
+        } else {
+            Some(Self {
+                etag: etag.map(|v| v.to_string()),
+                last_modified: last_modified.map(|v| v.to_string()),
+                links: Default::default(),
+            })
+        }
+    }
+}
+
+/// The store of the validators per url shared across crawls.
+pub trait ValidatorStore: std::fmt::Debug + Send + Sync {
+    /// Get the validators stored for the url.
+    fn get(&self, url: &str) -> Option<Validators>;
+    /// Store the validators of the url.
+    fn set(&self, url: &str, validators: Validators);
+}
+
+/// The validators stored in memory.
+#[derive(Debug, Default)]
+pub struct MemoryValidatorStore {
+    /// The validators per url.
+    entries: Mutex<HashMap<String, Validators>>,
+}
+
+impl MemoryValidatorStore {
+    /// Establish a new in memory validator store.
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Remove all the validators stored.
+    pub fn clear(&self) {
+        match self.entries.lock() {
+            Ok(mut entries) => entries.clear(),
+            _ => (),
+        }
+    }
+}
+
+impl ValidatorStore for MemoryValidatorStore {
+    fn get(&self, url: &str) -> Option<Validators> {
+        self.entries.lock().ok()?.get(url).cloned()
+    }
+
+    fn set(&self, url: &str, validators: Validators) {
+        match self.entries.lock() {
+            Ok(mut entries) => {
+                entries.insert(url.into(), validators);
+            }
+            _ => (),
+        }
+    }
+}
+
+/// The validators stored in memory and appended to a file to re-use between runs. The last line
+/// of the url in the file is used when loaded.
+#[derive(Debug)]
+pub struct DiskValidatorStore {
+    /// The validators loaded and stored per url.
+    memory: MemoryValidatorStore,
+    /// The path of the file persisting the validators.
+    path: PathBuf,
+    /// The file opened to append the validators stored.
+    file: Mutex<Option<std::fs::File>>,
+}
+
+impl DiskValidatorStore {
+    /// Establish a new validator store loading the validators persisted in the file at the path.
+    pub fn new<P: Into<PathBuf>>(path: P) -> Self {
+        let store = Self {
+            memory: Default::default(),
+            path: path.into(),
+            file: Default::default(),
+        };
+
+        match std::fs::read_to_string(&store.path) {
+            Ok(file) => {
+                for line in file.lines() {
+                    match parse(line) {
+                        Some((url, validators)) => store.memory.set(url, validators),
+                        _ => (),
+                    }
+                }
+            }
+            _ => (),
+        }
+
+        store
+    }
+
+    /// Append the line to the file creating the file and its directory when missing.
+    fn append(&self, line: &str) {
+        match self.file.lock() {
+            Ok(mut file) => {
+                if file.is_none() {
+                    match self.path.parent() {
+                        Some(dir) if !dir.as_os_str().is_empty() => {
+                            match std::fs::create_dir_all(dir) {
+                                _ => (),
+                            }
+                        }
+                        _ => (),
+                    }
+
+                    *file = std::fs::OpenOptions::new()
+                        .create(true)
+                        .append(true)
+                        .open(&self.path)
+                        .ok();
+                }
+
+                match file.as_mut() {
+                    Some(file) => match file.write_all(line.as_bytes()) {
+                        _ => (),
+                    },
+                    _ => (),
+                }
+            }
+            _ => (),
+        }
+    }
+}
+
+impl ValidatorStore for DiskValidatorStore {
+    fn get(&self, url: &str) -> Option<Validators> {
+        self.memory.get(url)
+    }
+
+    fn set(&self, url: &str, validators: Validators) {
+        let links: Vec<&str> = validators
+            .links
+            .iter()
+            .map(|link| link.inner().as_str())
+            .collect();
+
+        self.append(&format!(
+            "{}\t{}\t{}\t{}\n",
+            url,
+            validators.etag.as_deref().unwrap_or_default(),
+            validators.last_modified.as_deref().unwrap_or_default(),
+            links.join(" ")
+        ));
+        self.memory.set(url, validators);
+    }
+}
+
+/// Parse the line of the url with its validators and links split by tabs.
+fn parse(line: &str) -> Option<(&str, Validators)> {
+    let mut fields = line.splitn(4, '\t');
+    let url = fields.next().filter(|url| !url.is_empty())?;
+    let field = |field: Option<&str>| field.filter(|v| !v.is_empty()).map(|v| v.to_string());
+    let etag = field(fields.next());
+    let last_modified = field(fields.next());
+    let links = fields
+        .next()
+        .unwrap_or_default()
+        .split_ascii_whitespace()
+        .map(|link| link.into())
+        .collect();
+
+    Some((
+        url,
+        Validators {
+            etag,
+            last_modified,
+            links,
+        },
+    ))
+}
@@ -0,0 +1,355 @@
+use crate::features::diff::get_digest;
+use crate::CaseInsensitiveString;
+use hashbrown::HashSet;
+use std::sync::Mutex;
+
+/// The set of the links visited of a crawl.
+pub trait VisitedSet: std::fmt::Debug + Send + Sync {
+    /// Record the link visited. Returns true when the link was not visited before.
+    fn insert(&self, link: &CaseInsensitiveString) -> bool;
+    /// The link was visited.
+    fn contains(&self, link: &CaseInsensitiveString) -> bool;
+    /// Remove the link visited to visit it again. Returns true when the link was visited.
+    fn remove(&self, link: &CaseInsensitiveString) -> bool;
+    /// Get the count of the links visited.
+    fn len(&self) -> usize;
+    /// Remove all the links visited.
+    fn clear(&self);
+    /// No link was visited.
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// The links visited kept exactly in memory.
+#[derive(Debug, Default)]
+pub struct MemoryVisitedSet {
+    /// The links visited.
+    links: Mutex<HashSet<CaseInsensitiveString>>,
+}
+
+impl MemoryVisitedSet {
+    /// Establish a new empty visited set in memory.
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Get the links visited.
+    pub fn get_links(&self) -> HashSet<CaseInsensitiveString> {
+        match self.links.lock() {
+            Ok(links) => links.clone(),
+            _ => Default::default(),
+        }
+    }
+}
+
+impl VisitedSet for MemoryVisitedSet {
+    fn insert(&self, link: &CaseInsensitiveString) -> bool {
+        match self.links.lock() {
+            Ok(mut links) => links.insert(link.clone()),
+            _ => false,
+        }
+    }
+
+    fn contains(&self, link: &CaseInsensitiveString) -> bool {
+        match self.links.lock() {
+            Ok(links) => links.contains(link),
+            _ => false,
+        }
+    }
+
+    fn remove(&self, link: &CaseInsensitiveString) -> bool {
+        match self.links.lock() {
+            Ok(mut links) => links.remove(link),
+            _ => false,
+        }
+    }
+
+    fn len(&self) -> usize {
+        match self.links.lock() {
+            Ok(links) => links.len(),
+            _ => 0,
+        }
+    }
+
+    fn clear(&self) {
+        match self.links.lock() {
+            Ok(mut links) => links.clear(),
+            _ => (),
+        }
+    }
+}
+
+/// The bits of the Bloom filter with the links removed to visit again.
+#[derive(Debug, Default)]
+struct BloomBits {
+    /// The bits packed by 64 per word.
+    bits: Vec<u64>,
+    /// The links removed lowercase not visited again. The bits of a link are shared with the other
+    /// links and never cleared.
+    removed: HashSet<String>,
+    /// The count of the links inserted.
+    len: usize,
+}
+
+impl BloomBits {
+    /// The bit at the index is set.
+    fn get(&self, index: usize) -> bool {
+        self.bits[index / 64] & (1 << (index % 64)) != 0
+    }
+
+    /// Set the bit at the index.
+    fn set(&mut self, index: usize) {
+        self.bits[index / 64] |= 1 << (index % 64);
+    }
+}
+
+/// The hashes of the links visited kept in a Bloom filter bounding the memory of the crawl to about
+/// 1.2 bytes per link at a false positive rate of 1%. The links removed are kept exactly until they
+/// are visited again.
+#[derive(Debug)]
+pub struct BloomVisitedSet {
+    /// The bits of the filter.
+    bits: Mutex<BloomBits>,
+    /// The count of the bits.
+    size: usize,
+    /// The count of the bits set per link.
+    hashes: usize,
+}
+
+impl BloomVisitedSet {
+    /// Establish a new empty Bloom filter sized for the links expected at the false positive rate
+    /// like `0.01`. The false positive rate grows over the links expected.
+    pub fn new(capacity: usize, false_positive_rate: f64) -> Self {
+        let capacity = capacity.max(1) as f64;
+        let false_positive_rate = if false_positive_rate > 0.0 && false_positive_rate < 1.0 {
+            false_positive_rate
+        } else {
+            0.01
+        };
+        let ln2 = std::f64::consts::LN_2;
+        let size = (-capacity * false_positive_rate.ln() / (ln2 * ln2))
+            .ceil()
+            .max(64.0) as usize;
+        let hashes = (size as f64 / capacity * ln2).round().clamp(1.0, 32.0) as usize;
+
+        Self {
+            bits: Mutex::new(BloomBits {
+                bits: vec![0; (size + 63) / 64],
+                removed: Default::default(),
+                len: 0,
+            }),
+            size,
+            hashes,
+        }
+    }
+
+    /// Get the count of the bits.
+    pub fn get_size(&self) -> usize {
+        self.size
+    }
+
+    /// Get the count of the bits set per link.
+    pub fn get_hashes(&self) -> usize {
+        self.hashes
+    }
+
+    /// Get the indexes of the bits of the link by double hashing its digest.
+    fn get_indexes(&self, link: &str) -> Vec<usize> {
+        let digest = get_digest(link.as_bytes());
+        let (h1, h2) = (digest, digest.rotate_left(32) | 1);
+
+        (0..self.hashes as u64)
+            .map(|i| (h1.wrapping_add(i.wrapping_mul(h2)) % self.size as u64) as usize)
+            .collect()
+    }
+}
+
+impl VisitedSet for BloomVisitedSet {
+    fn insert(&self, link: &CaseInsensitiveString) -> bool {
+        let link = link.inner().to_ascii_lowercase();
+        let indexes = self.get_indexes(&link);
+
+        match self.bits.lock() {
+            Ok(mut bits) => {
+                if bits.removed.remove(&link) {
+                    bits.len += 1;
+                    return true;
+                }
+
+                if indexes.iter().all(|index| bits.get(*index)) {
+                    return false;
+                }
+
+                for index in indexes {
+                    bits.set(index);
+                }
+                bits.len += 1;
+
+                true
+            }
+            _ => false,
+        }
+    }
+
+    fn contains(&self, link: &CaseInsensitiveString) -> bool {
+        let link = link.inner().to_ascii_lowercase();
+        let indexes = self.get_indexes(&link);
+
+        match self.bits.lock() {
+            Ok(bits) => {
+                !bits.removed.contains(&link) && indexes.iter().all(|index| bits.get(*index))
+            }
+            _ => false,
+        }
+    }
+
+    /// Remove the link visited keeping its bits set for the other links sharing them. The link is
+    /// kept exactly until it is visited again.
+    fn remove(&self, link: &CaseInsensitiveString) -> bool {
+        let link = link.inner().to_ascii_lowercase();
+        let indexes = self.get_indexes(&link);
+
+        match self.bits.lock() {
+            Ok(mut bits) => {
+                if bits.removed.contains(&link) || !indexes.iter().all(|index| bits.get(*index)) {
+                    return false;
+                }
+
+                bits.removed.insert(link);
+                bits.len = bits.len.saturating_sub(1);
+
+                true
+            }
+            _ => false,
+        }
+    }
+
+    fn len(&self) -> usize {
+        match self.bits.lock() {
+            Ok(bits) => bits.len,
+            _ => 0,
+        }
+    }
+
+    fn clear(&self) {
+        match self.bits.lock() {
+            Ok(mut bits) => {
+                bits.bits.fill(0);
+                bits.removed.clear();
+                bits.len = 0;
+            }
+            _ => (),
+        }
+    }
+}
+
+#[cfg(feature = "sled")]
+/// The links visited kept on disk in a sled database surviving the restarts of the crawls.
+#[derive(Debug)]
+pub struct SledVisitedSet {
+    /// The database of the links visited lowercase.
+    db: sled::Db,
+}
+
+#[cfg(feature = "sled")]
+impl SledVisitedSet {
+    /// Open the database at the path creating it when missing. The links visited recorded before
+    /// are kept. A database is opened by a single process at once.
+    pub fn new<P: AsRef<std::path::Path>>(path: P) -> sled::Result<Self> {
+        Ok(Self {
+            db: sled::open(path)?,
+        })
+    }
+
+    /// Flush the links visited recorded to the disk. The links are flushed in the background
+    /// periodically and when the set is dropped.
+    pub fn flush(&self) -> sled::Result<usize> {
+        self.db.flush()
+    }
+}
+
+#[cfg(feature = "sled")]
+impl VisitedSet for SledVisitedSet {
+    fn insert(&self, link: &CaseInsensitiveString) -> bool {
+        let key = link.inner().to_lowercase();
+
+        // the previous value is swapped atomically for a single task to claim the link
+        match self.db.insert(key.as_bytes(), &b""[..]) {
+            Ok(previous) => previous.is_none(),
+            _ => false,
+        }
+    }
+
+    fn contains(&self, link: &CaseInsensitiveString) -> bool {
+        self.db
+            .contains_key(link.inner().to_lowercase().as_bytes())
+            .unwrap_or_default()
+    }
+
+    fn remove(&self, link: &CaseInsensitiveString) -> bool {
+        match self.db.remove(link.inner().to_lowercase().as_bytes()) {
+            Ok(previous) => previous.is_some(),
+            _ => false,
+        }
+    }
+
+    /// Get the count of the links visited scanning the database.
+    fn len(&self) -> usize {
+        self.db.len()
+    }
+
+    fn clear(&self) {
+        let _ = self.db.clear();
+    }
+
+    fn is_empty(&self) -> bool {
+        self.db.is_empty()
+    }
+}
+
+#[test]
+fn test_visited_set() {
+    let bloom = BloomVisitedSet::new(10_000, 0.01);
+    let link: CaseInsensitiveString = "https://choosealicense.com/licenses/".into();
+
+    assert_eq!(bloom.get_hashes(), 7);
+    assert!(bloom.insert(&link));
+    assert!(!bloom.insert(&"https://CHOOSEALICENSE.com/licenses/".into()));
+    assert!(bloom.contains(&link));
+    assert_eq!(bloom.len(), 1);
+    assert!(bloom.remove(&link));
+    assert!(!bloom.contains(&link));
+    assert!(!bloom.remove(&link));
+    assert!(bloom.is_empty());
+    assert!(bloom.insert(&link));
+    assert!(bloom.contains(&link));
+    bloom.clear();
+
+    let small = BloomVisitedSet::new(1, 0.5);
+    let links: Vec<CaseInsensitiveString> = (0..100)
+        .map(|i| format!("https://choosealicense.com/{}/", i).into())
+        .collect();
+    let inserted: Vec<&CaseInsensitiveString> =
+        links.iter().filter(|link| small.insert(link)).collect();
+    let false_positive = links
+        .iter()
+        .find(|link| !inserted.contains(link))
+        .expect("false positive");
+
+    assert!(small.remove(false_positive));
+    assert!(inserted.iter().all(|link| small.contains(link)));
+
+    for i in 0..10_000 {
+        bloom.insert(&format!("https://choosealicense.com/{}/", i).into());
+    }
+
+    let false_positives = (10_000..20_000)
+        .filter(|i| bloom.contains(&format!("https://choosealicense.com/{}/", i).into()))
+        .count();
+
+    assert!(false_positives < 300, "{}", false_positives);
+
+    bloom.clear();
+    assert!(!bloom.contains(&"https://choosealicense.com/1/".into()));
+}
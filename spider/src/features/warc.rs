@@ -0,0 +1,197 @@
+use crate::features::middleware::CrawlMiddleware;
+use crate::utils::{get_iso_date, log, PageResponse};
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use reqwest::header::{CONTENT_ENCODING, CONTENT_LENGTH, TRANSFER_ENCODING};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::SystemTime;
+
+/// The middleware writing the responses of the crawl to a WARC/1.1 file.
+#[derive(Debug)]
+pub struct WarcWriter {
+    /// The path of the WARC file.
+    path: PathBuf,
+    /// The file opened to append the records.
+    file: Mutex<std::fs::File>,
+}
+
+impl WarcWriter {
+    /// Establish a new WARC writer creating the file at the path and its directory when missing
+    /// starting with a `warcinfo` record.
+    pub fn new<P: Into<PathBuf>>(path: P) -> std::io::Result<Self> {
+        let path = path.into();
+
+        match path.parent() {
+            Some(dir) if !dir.as_os_str().is_empty() => std::fs::create_dir_all(dir)?,
+            _ => (),
+        }
+
+        let writer = Self {
+            file: Mutex::new(std::fs::File::create(&path)?),
+            path,
+        };
+        let info = format!(
+            "software: spider/{}\r\nformat: WARC File Format 1.1\r\n",
+            env!("CARGO_PKG_VERSION")
+        );
+        let filename = writer
+            .path
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_default();
+
+        writer.write_record(
+            &[
+                ("WARC-Type", "warcinfo"),
+                ("WARC-Filename", &filename),
+                ("Content-Type", "application/warc-fields"),
+            ],
+            &[info.as_bytes()],
+        )?;
+
+        Ok(writer)
+    }
+
+    /// Get the path of the WARC file.
+    pub fn get_path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Write the record with the headers and the parts of its block as a gzip member.
+    fn write_record(&self, headers: &[(&str, &str)], block: &[&[u8]]) -> std::io::Result<()> {
+        let length: usize = block.iter().map(|part| part.len()).sum();
+        let mut record = format!(
+            "WARC/1.1\r\nWARC-Record-ID: <urn:uuid:{}>\r\nWARC-Date: {}\r\n",
+            get_uuid(),
+            get_iso_date(SystemTime::now())
+        );
+
+        for (name, value) in headers {
+            record.push_str(&format!("{}: {}\r\n", name, value));
+        }
+        record.push_str(&format!("Content-Length: {}\r\n\r\n", length));
+
+        let mut file = match self.file.lock() {
+            Ok(file) => file,
+            Err(e) => e.into_inner(),
+        };
+        let mut encoder = GzEncoder::new(&mut *file, Compression::default());
+
+        encoder.write_all(record.as_bytes())?;
+        for part in block {
+            encoder.write_all(part)?;
+        }
+        encoder.write_all(b"\r\n\r\n")?;
+        encoder.finish()?;
+
+        Ok(())
+    }
+}
+
+impl CrawlMiddleware for WarcWriter {
+    fn after_response(&self, url: &str, response: &mut PageResponse) {
+        let headers = match response.headers.as_ref() {
+            Some(headers) => headers,
+            _ => return,
+        };
+        let mut head = format!(
+            "{:?} {} {}\r\n",
+            response.http_version.unwrap_or(reqwest::Version::HTTP_11),
+            response.status_code.as_u16(),
+            response.status_code.canonical_reason().unwrap_or_default()
+        );
+
+        // the body is written decoded from its transfer encoding and its content encoding
+        for (name, value) in headers.iter().filter(|(name, _)| {
+            **name != TRANSFER_ENCODING
+                && (response.content_encoding.is_none()
+                    || **name != CONTENT_ENCODING && **name != CONTENT_LENGTH)
+        }) {
+            head.push_str(name.as_str());
+            head.push_str(": ");
+            head.push_str(&String::from_utf8_lossy(value.as_bytes()));
+            head.push_str("\r\n");
+        }
+        head.push_str("\r\n");
+
+        let target = response.final_url.as_deref().unwrap_or(url);
+        let body = response.content.as_deref().unwrap_or_default();
+
+        match self.write_record(
+            &[
+                ("WARC-Type", "response"),
+                ("WARC-Target-URI", target),
+                ("Content-Type", "application/http;msgtype=response"),
+            ],
+            &[head.as_bytes(), body],
+        ) {
+            Ok(_) => (),
+            _ => log("- error writing the warc record {}", target),
+        }
+    }
+}
+
+/// Get a random version 4 uuid.
+fn get_uuid() -> String {
+    let uuid = fastrand::u128(..);
+    // set the version 4 and the variant bits
+    let uuid = (uuid & !(0xf000 << 64) & !(0xc000 << 48)) | (0x4000 << 64) | (0x8000 << 48);
+    let hex: String = uuid
+        .to_be_bytes()
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect();
+
+    format!(
+        "{}-{}-{}-{}-{}",
+        &hex[..8],
+        &hex[8..12],
+        &hex[12..16],
+        &hex[16..20],
+        &hex[20..]
+    )
+}
+
+#[test]
+fn test_warc_writer() {
+    use reqwest::header::{HeaderMap, HeaderValue};
+    use std::io::Read;
+
+    let path = std::env::temp_dir().join("spider_test_warc_writer.warc.gz");
+    let writer = WarcWriter::new(&path).unwrap();
+    let mut headers = HeaderMap::new();
+    headers.insert("content-type", HeaderValue::from_static("text/html"));
+    headers.insert(TRANSFER_ENCODING, HeaderValue::from_static("chunked"));
+    headers.insert(CONTENT_ENCODING, HeaderValue::from_static("gzip"));
+
+    writer.after_response(
+        "https://choosealicense.com/",
+        &mut crate::utils::PageResponse {
+            content: Some(bytes::Bytes::from_static(b"<html></html>")),
+            status_code: reqwest::StatusCode::OK,
+            headers: Some(headers),
+            http_version: Some(reqwest::Version::HTTP_11),
+            content_encoding: Some(crate::utils::ContentEncoding::Gzip),
+            ..Default::default()
+        },
+    );
+    // the responses without headers are not written
+    writer.after_response("https://choosealicense.com/about/", &mut Default::default());
+
+    let mut warc = String::new();
+    flate2::read::MultiGzDecoder::new(std::fs::File::open(&path).unwrap())
+        .read_to_string(&mut warc)
+        .unwrap();
+    let records: Vec<&str> = warc.split("WARC/1.1\r\n").skip(1).collect();
+
+    assert_eq!(records.len(), 2);
+    assert!(records[0].contains("WARC-Type: warcinfo\r\n"));
+    assert!(records[1].contains("WARC-Target-URI: https://choosealicense.com/\r\n"));
+    assert!(records[1].ends_with(
+        "Content-Length: 57\r\n\r\nHTTP/1.1 200 OK\r\ncontent-type: text/html\r\n\r\n<html></html>\r\n\r\n"
+    ));
+
+    let _ = std::fs::remove_file(path);
+}
@@ -0,0 +1,208 @@
+use crate::utils::{escape_json, get_iso_date, glob_match_url, log};
+use compact_str::CompactString;
+use std::sync::Mutex;
+
+/// The events of a crawl notified by a webhook.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct WebhookEvents {
+    /// Notify the crawl started.
+    pub started: bool,
+    /// Notify the crawl finished with its status and its totals.
+    pub finished: bool,
+    /// Notify every N pages crawled. Use 0 to skip.
+    pub every_pages: u64,
+    /// Notify the ratio of the requests failed crossing the threshold like 0.2 once until it falls
+    /// under the threshold again.
+    pub error_rate: Option<f64>,
+    /// The pages crawled before the error rate is checked for the first requests not to cross it.
+    pub error_rate_pages: u64,
+    /// Notify the pages crawled matching a glob pattern like `/blog/*` or `*.pdf`.
+    pub url_patterns: Vec<CompactString>,
+}
+
+/// The totals of the crawl notified.
+#[derive(Debug, Default)]
+struct WebhookState {
+    /// The pages crawled.
+    pages: u64,
+    /// The requests failed.
+    failures: u64,
+    /// The error rate is over the threshold.
+    error_rate_crossed: bool,
+    /// The requests sending the events in the background.
+    deliveries: Vec<tokio::task::JoinHandle<()>>,
+}
+
+/// The webhook POSTing the events of the crawls to its url.
+#[derive(Debug)]
+pub struct Webhook {
+    /// The url receiving the events.
+    url: String,
+    /// The events notified.
+    events: WebhookEvents,
+    /// The client sending the events.
+    client: reqwest::Client,
+    /// The totals of the crawl notified.
+    state: Mutex<WebhookState>,
+}
+
+impl Webhook {
+    /// Establish the webhook POSTing the events to the url.
+    pub fn new(url: &str, events: WebhookEvents) -> Self {
+        Self {
+            url: url.into(),
+            events,
+            client: Default::default(),
+            state: Default::default(),
+        }
+    }
+
+    /// Get the events notified.
+    pub fn get_events(&self) -> &WebhookEvents {
+        &self.events
+    }
+
+    /// Wait for the events sent in the background to be delivered.
+    pub async fn flush(&self) {
+        let deliveries = match self.state.lock() {
+            Ok(mut state) => std::mem::take(&mut state.deliveries),
+            _ => Default::default(),
+        };
+
+        for delivery in deliveries {
+            let _ = delivery.await;
+        }
+    }
+
+    /// Handle the crawl of the url started resetting the totals.
+    pub(crate) fn on_started(&self, url: &str) {
+        match self.state.lock() {
+            Ok(mut state) => {
+                state.pages = 0;
+                state.failures = 0;
+                state.error_rate_crossed = false;
+            }
+            _ => (),
+        }
+
+        if self.events.started {
+            self.send(format!(
+                r#"{{"event":"started","url":"{}","timestamp":"{}"}}"#,
+                escape_json(url),
+                get_iso_date(std::time::SystemTime::now())
+            ));
+        }
+    }
+
+    /// Handle the page of the link crawled by the crawl of the url.
+    pub(crate) fn on_page(&self, url: &str, link: &str, failed: bool) {
+        for event in self.get_page_events(url, link, failed) {
+            self.send(event);
+        }
+    }
+
+    /// Count the page of the link crawled getting the events it notifies.
+    pub(crate) fn get_page_events(&self, url: &str, link: &str, failed: bool) -> Vec<String> {
+        let mut events = Vec::new();
+
+        match self.state.lock() {
+            Ok(mut state) => {
+                state.pages += 1;
+
+                if failed {
+                    state.failures += 1;
+                }
+
+                if self.events.every_pages > 0 && state.pages % self.events.every_pages == 0 {
+                    events.push(format!(
+                        r#"{{"event":"pages","url":"{}","pages":{},"failures":{}}}"#,
+                        escape_json(url),
+                        state.pages,
+                        state.failures
+                    ));
+                }
+
+                match self.events.error_rate {
+                    Some(threshold) if state.pages >= self.events.error_rate_pages.max(1) => {
+                        let error_rate = state.failures as f64 / state.pages as f64;
+
+                        if error_rate >= threshold && !state.error_rate_crossed {
+                            events.push(format!(
+                                r#"{{"event":"error_rate","url":"{}","pages":{},"failures":{},"error_rate":{}}}"#,
+                                escape_json(url),
+                                state.pages,
+                                state.failures,
+                                error_rate
+                            ));
+                        }
+
+                        state.error_rate_crossed = error_rate >= threshold;
+                    }
+                    _ => (),
+                }
+            }
+            _ => (),
+        }
+
+        for pattern in self.events.url_patterns.iter() {
+            if glob_match_url(pattern, link) {
+                events.push(format!(
+                    r#"{{"event":"url_matched","url":"{}","link":"{}","pattern":"{}"}}"#,
+                    escape_json(url),
+                    escape_json(link),
+                    escape_json(pattern)
+                ));
+            }
+        }
+
+        events
+    }
+
+    /// Handle the crawl of the url finished with the status.
+    pub(crate) fn on_finished(&self, url: &str, status: &str) {
+        if !self.events.finished {
+            return;
+        }
+
+        let (pages, failures) = match self.state.lock() {
+            Ok(state) => (state.pages, state.failures),
+            _ => Default::default(),
+        };
+
+        self.send(format!(
+            r#"{{"event":"finished","url":"{}","status":"{}","pages":{},"failures":{},"timestamp":"{}"}}"#,
+            escape_json(url),
+            escape_json(status),
+            pages,
+            failures,
+            get_iso_date(std::time::SystemTime::now())
+        ));
+    }
+
+    /// Send the event in the background of the runtime.
+    fn send(&self, event: String) {
+        let handle = match tokio::runtime::Handle::try_current() {
+            Ok(handle) => handle,
+            _ => return,
+        };
+        let request = self
+            .client
+            .post(&self.url)
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .body(event);
+        let delivery = handle.spawn(async move {
+            match request.send().await.and_then(|res| res.error_for_status()) {
+                Err(e) => log("webhook send failed - ", e.to_string()),
+                _ => (),
+            }
+        });
+
+        match self.state.lock() {
+            Ok(mut state) => {
+                state.deliveries.retain(|delivery| !delivery.is_finished());
+                state.deliveries.push(delivery);
+            }
+            _ => (),
+        }
+    }
+}
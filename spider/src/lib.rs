@@ -45,15 +45,17 @@
 //! ## Feature flags
 //!
 //! - `ua_generator`: Enables auto generating a random real User-Agent.
-//! - `regex`: Enables blacklisting paths with regx
+//! - `regex`: Enables blacklisting and whitelisting paths with regx
 //! - `jemalloc`: Enables the [jemalloc](https://github.com/jemalloc/jemalloc) memory backend.
 //! - `decentralized`: Enables decentralized processing of IO, requires the [spider_worker](../spider_worker/README.md) startup before crawls.
 //! - `sync`: Subscribe to changes for Page data processing async.
-//! - `budget`: Allows setting a crawl budget per path with depth.
+//! - `budget`: Allows setting a crawl budget per path with depth and per link hops from the start url.
 //! - `control`: Enables the ability to pause, start, and shutdown crawls on demand.
 //! - `full_resources`: Enables gathering all content that relates to the domain like css,jss, and etc.
-//! - `serde`: Enables serde serialization support.
-//! - `socks`: Enables socks5 proxy support.
+//! - `serde`: Enables serde serialization support including the `Configuration`.
+//! - `socks`: Enables the `socks5://` and `socks5h://` proxies with the credentials of their user info.
+//! - `tor`: Enables routing the requests through the SOCKS port of Tor requesting new circuits with the control port.
+//! - `dns`: Enables resolving the hosts with custom nameservers or DNS-over-HTTPS.
 //! - `glob`: Enables [url glob](https://everything.curl.dev/cmdline/globbing) support.
 //! - `fs`: Enables storing resources to disk for parsing (may greatly increases performance at the cost of temp storage). Enabled by default.
 //! - `sitemap`: Include sitemap pages in results.
@@ -62,11 +64,31 @@
 //! - `chrome`: Enables chrome headless rendering, use the env var `CHROME_URL` to connect remotely [experimental].
 //! - `chrome_headed`: Enables chrome rendering headful rendering [experimental].
 //! - `chrome_cpu`: Disable gpu usage for chrome browser.
-//! - `chrome_stealth`: Enables stealth mode to make it harder to be detected as a bot.
+//! - `chrome_stealth`: Enables stealth mode by default to make it harder to be detected as a bot.
 //! - `chrome_screenshot`: Enables storing a screenshot of each page on crawl. Defaults the screenshots to the ./storage/ directory. Use the env variable `SCREENSHOT_DIRECTORY` to adjust the directory.
-//! - `cookies`: Enables cookies storing and setting to use for request.
-//! - `cron`: Enables the ability to start cron jobs for the website.
-//! - `http3`: Enables experimental HTTP/3 client.
+//! - `cookies`: Enables cookies storing and setting to use for request. Cookies can be saved and loaded between runs.
+//! - `cron`: Enables the ability to start cron jobs for the website and the `CrawlJob` runner.
+//! - `http3`: Enables experimental HTTP/3 client. Use `website.with_http3_prior_knowledge` to request the website with HTTP/3 falling back to HTTP/2 or HTTP/1.1.
+//! - `zstd`: Enables accepting and decoding the zstd content encoding of the pages. The encoding decoded is set on `page.content_encoding`.
+//! - `charset_detection`: Enables guessing the charset of the pages without a declared charset before transcoding them to UTF-8.
+//! - `language`: Enables detecting the language of the pages and `website.with_languages` to only follow the links of the pages of the languages.
+//! - `text_extraction`: Enables extracting the title and the text of the main content of the pages without the boilerplate.
+//! - `markdown`: Enables converting the pages to markdown with their headings, lists, links, and code blocks.
+//! - `dedup`: Enables detecting the duplicate and the near duplicate pages to skip storing them or following their links.
+//! - `redis`: Enables the Redis frontier and visited set shared by the crawls of several processes.
+//! - `coordinator`: Enables the coordinator of the distributed crawls leasing the links to the workers.
+//! - `kafka`: Enables the Kafka sink producing the record of every page crawled to a topic.
+//! - `nats`: Enables the NATS sink publishing the record of every page crawled to a subject.
+//! - `s3`: Enables the S3 sink storing the body of the pages crawled as the objects of an S3 or MinIO bucket.
+//! - `sqlite`: Enables writing the pages, the links, the statuses, and the timings of the crawl to a SQLite database.
+//! - `sled`: Enables the sled visited set keeping the links visited on disk across the restarts of the crawls.
+//! - `tracing`: Enables the tracing spans of the crawls, the requests, the parsing of the pages, and the frontier.
+//! - `opentelemetry`: Enables exporting the tracing spans of the crawls with OTLP to a collector.
+//! - `grpc`: Enables the gRPC service controlling the crawls of a long-lived daemon.
+//! - `server`: Enables the HTTP server with a JSON API controlling the crawls of a long-lived daemon.
+//! - `config_files`: Enables loading the `Configuration` from TOML, YAML, or JSON files.
+//! - `signing`: Enables the `HmacSigner` and the AWS `SigV4Signer` middlewares signing the requests.
+//! - `oauth2`: Enables the OAuth2 client credentials grant of the bearer token of the requests refreshed automatically.
 
 pub extern crate bytes;
 pub extern crate compact_str;
@@ -88,6 +110,12 @@ pub extern crate flexbuffers;
 #[cfg(feature = "serde")]
 pub extern crate serde;
 
+#[cfg(any(feature = "structured_data", feature = "coordinator"))]
+pub extern crate serde_json;
+
+#[cfg(feature = "s3")]
+pub extern crate s3;
+
 pub extern crate case_insensitive_string;
 pub extern crate smallvec;
 pub extern crate url;
@@ -142,8 +170,31 @@ pub mod black_list {
 pub mod black_list {
     use compact_str::CompactString;
 
-    /// check if link exist in blacklists.
+    /// check if link exist in blacklists or matches a glob pattern like `/blog/*/comments` or `*.pdf` of the blacklists.
     pub fn contains(blacklist_url: &Vec<CompactString>, link: &CompactString) -> bool {
-        blacklist_url.contains(&link)
+        blacklist_url.iter().any(|b| {
+            b == link || (crate::utils::is_glob(b) && crate::utils::glob_match_url(b, link))
+        })
+    }
+}
+
+#[cfg(feature = "regex")]
+/// White list checking url exist with Regex.
+pub mod white_list {
+    use compact_str::CompactString;
+    /// check if link exist in whitelists with regex.
+    pub fn contains(whitelist_url: &regex::RegexSet, link: &CompactString) -> bool {
+        whitelist_url.is_match(link)
+    }
+}
+
+#[cfg(not(feature = "regex"))]
+/// White list checking url exist.
+pub mod white_list {
+    use compact_str::CompactString;
+
+    /// check if link starts with a pattern in the whitelists.
+    pub fn contains(whitelist_url: &Vec<CompactString>, link: &CompactString) -> bool {
+        whitelist_url.iter().any(|w| link.starts_with(w.as_str()))
     }
 }
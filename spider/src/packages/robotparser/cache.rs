@@ -0,0 +1,199 @@
+//! robots.txt cache shared across crawls.
+
+use super::parser::RobotFileParser;
+use hashbrown::HashMap;
+use reqwest::header::CACHE_CONTROL;
+use reqwest::{Client, StatusCode};
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// The cache of the robots.txt rules per origin shared across `Website` instances.
+#[derive(Debug)]
+pub struct RobotsCache {
+    /// The parsed robots.txt per origin with the unix time in seconds they expire at.
+    entries: Mutex<HashMap<String, (Box<RobotFileParser>, u64)>>,
+    /// The max duration to cache a robots.txt. Defaults to 24 hours.
+    ttl: Duration,
+    /// The directory to persist the robots.txt files between runs.
+    dir: Option<PathBuf>,
+}
+
+impl Default for RobotsCache {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl RobotsCache {
+    /// Establish a new in memory robots.txt cache.
+    pub fn new() -> Self {
+        Self {
+            entries: Default::default(),
+            ttl: Duration::from_secs(86400),
+            dir: None,
+        }
+    }
+
+    /// Set the max duration to cache a robots.txt.
+    pub fn with_ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = ttl;
+        self
+    }
+
+    /// Set the directory to persist the robots.txt files between runs.
+    pub fn with_dir<P: Into<PathBuf>>(mut self, dir: P) -> Self {
+        self.dir = Some(dir.into());
+        self
+    }
+
+    /// Remove all the cached robots.txt from memory.
+    pub fn clear(&self) {
+        match self.entries.lock() {
+            Ok(mut entries) => entries.clear(),
+            _ => (),
+        }
+    }
+
+    /// Get the parsed robots.txt of the origin url ending with a slash, fetching it when missing
+    /// or expired. A stale robots.txt is re-used when the origin is unreachable.
+    pub async fn get(&self, client: &Client, url: &str) -> Box<RobotFileParser> {
+        let now = unix_now();
+        let cached = match self.entries.lock() {
+            Ok(entries) => entries.get(url).cloned(),
+            _ => None,
+        };
+        let cached = match cached {
+            Some(cached) => Some(cached),
+            _ => self.load(url),
+        };
+
+        match cached {
+            Some((robot_file_parser, expires_at)) if expires_at > now => {
+                self.insert(url, &robot_file_parser, expires_at);
+                robot_file_parser
+            }
+            cached => match fetch(client, url).await {
+                Some((status, text, max_age)) => {
+                    let ttl = match max_age {
+                        Some(max_age) => max_age.min(self.ttl),
+                        _ => self.ttl,
+                    };
+                    let expires_at = now + ttl.as_secs();
+                    let robot_file_parser = parse(status, &text);
+
+                    self.insert(url, &robot_file_parser, expires_at);
+                    self.store(url, status, &text, expires_at);
+
+                    robot_file_parser
+                }
+                _ => match cached {
+                    Some((robot_file_parser, _)) => robot_file_parser,
+                    _ => {
+                        let mut robot_file_parser = RobotFileParser::new();
+                        robot_file_parser.modified();
+                        robot_file_parser
+                    }
+                },
+            },
+        }
+    }
+
+    /// Insert the parsed robots.txt in memory.
+    fn insert(&self, url: &str, robot_file_parser: &RobotFileParser, expires_at: u64) {
+        match self.entries.lock() {
+            Ok(mut entries) => {
+                entries.insert(url.into(), (Box::new(robot_file_parser.clone()), expires_at));
+            }
+            _ => (),
+        }
+    }
+
+    /// The path of the persisted robots.txt of the origin.
+    fn get_path(&self, url: &str) -> Option<PathBuf> {
+        let file_name: String = url
+            .chars()
+            .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+            .collect();
+
+        Some(self.dir.as_ref()?.join(format!("{}.txt", file_name)))
+    }
+
+    /// Load the persisted robots.txt of the origin.
+    fn load(&self, url: &str) -> Option<(Box<RobotFileParser>, u64)> {
+        let file = std::fs::read_to_string(self.get_path(url)?).ok()?;
+        let (meta, text) = file.split_once('\n')?;
+        let (status, expires_at) = meta.split_once(' ')?;
+        let status = StatusCode::from_u16(status.parse().ok()?).ok()?;
+
+        Some((parse(status, text), expires_at.parse().ok()?))
+    }
+
+    /// Persist the robots.txt of the origin.
+    fn store(&self, url: &str, status: StatusCode, text: &str, expires_at: u64) {
+        match self.get_path(url) {
+            Some(path) => {
+                match &self.dir {
+                    Some(dir) => match std::fs::create_dir_all(dir) {
+                        _ => (),
+                    },
+                    _ => (),
+                };
+                let file = format!("{} {}\n{}", status.as_u16(), expires_at, text);
+
+                match std::fs::write(path, file) {
+                    _ => (),
+                };
+            }
+            _ => (),
+        }
+    }
+}
+
+/// Parse the robots.txt response.
+fn parse(status: StatusCode, text: &str) -> Box<RobotFileParser> {
+    let mut robot_file_parser = RobotFileParser::new();
+    robot_file_parser.modified();
+    robot_file_parser.from_status(status, text);
+    robot_file_parser
+}
+
+/// Fetch the robots.txt of the origin with the max-age of the response. Returns None when the
+/// origin is unreachable or fails with a server error.
+async fn fetch(client: &Client, url: &str) -> Option<(StatusCode, String, Option<Duration>)> {
+    let res = client
+        .get(&string_concat!(url, "robots.txt"))
+        .send()
+        .await
+        .ok()?;
+    let status = res.status();
+
+    if status.is_server_error() {
+        return None;
+    }
+
+    let max_age = match res.headers().get(CACHE_CONTROL) {
+        Some(cache_control) => cache_control.to_str().ok().and_then(|cache_control| {
+            cache_control.split(',').find_map(|directive| {
+                let max_age = directive.trim().strip_prefix("max-age=")?;
+                Some(Duration::from_secs(max_age.parse().ok()?))
+            })
+        }),
+        _ => None,
+    };
+    let text = if status == StatusCode::OK {
+        res.text().await.ok()?
+    } else {
+        Default::default()
+    };
+
+    Some((status, text, max_age))
+}
+
+/// The current unix time in seconds.
+fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
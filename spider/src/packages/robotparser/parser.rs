@@ -34,6 +34,7 @@ use std::time::{Duration, SystemTime, UNIX_EPOCH};
 /// A rule line is a single "Allow:" (allowance==True) or "Disallow:"
 /// (allowance==False) followed by a path."""
 #[derive(Debug, Eq, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 struct RuleLine {
     /// Path of the rule
     path: String,
@@ -42,6 +43,7 @@ struct RuleLine {
 }
 
 #[derive(Debug, Eq, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 /// Determine the amount of request allowed between navigation or crawls.
 pub struct RequestRate {
     /// Amount of request allowed within duration
@@ -52,6 +54,7 @@ pub struct RequestRate {
 
 /// An entry has one or more user-agents and zero or more rulelines
 #[derive(Debug, Eq, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 struct Entry {
     /// Multiple user agents to use
     useragents: Vec<String>,
@@ -65,6 +68,7 @@ struct Entry {
 
 /// robots.txt file parser
 #[derive(Debug, Eq, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct RobotFileParser {
     /// Entire robots.txt list of urls
     entries: Vec<Entry>,
@@ -76,6 +80,8 @@ pub struct RobotFileParser {
     allow_all: bool,
     /// Time last checked robots.txt file
     last_checked: i64,
+    /// The urls of the `Sitemap` directives in order
+    sitemaps: Vec<String>,
 }
 
 impl RuleLine {
@@ -87,10 +93,42 @@ impl RuleLine {
     }
 
     fn applies_to(&self, filename: &str) -> bool {
-        self.path == "*" || filename.starts_with(&self.path)
+        self.path == "*" || matches_path(&self.path, filename)
     }
 }
 
+/// Match the path with the pattern of a rule per RFC 9309 with `*` matching any characters and a
+/// trailing `$` matching the end of the path.
+fn matches_path(pattern: &str, path: &str) -> bool {
+    let (pattern, anchored) = match pattern.strip_suffix('$') {
+        Some(pattern) => (pattern, true),
+        _ => (pattern, false),
+    };
+    let mut parts = pattern.split('*');
+    let first = parts.next().unwrap_or_default();
+
+    if !path.starts_with(first) {
+        return false;
+    }
+
+    let parts: Vec<&str> = parts.collect();
+    let mut position = first.len();
+
+    for (i, part) in parts.iter().enumerate() {
+        // the last part of an anchored pattern matches the end of the path
+        if anchored && i == parts.len() - 1 {
+            return path.len() - position >= part.len() && path.ends_with(part);
+        }
+
+        match path[position..].find(part) {
+            Some(found) => position += found + part.len(),
+            _ => return false,
+        }
+    }
+
+    !anchored || position == path.len()
+}
+
 impl Entry {
     /// Base collection to manage robot.txt data
     fn new() -> Entry {
@@ -120,16 +158,27 @@ impl Entry {
         false
     }
 
+    /// Get the length of the longest rule matching the filename with its allowance with the
+    /// allow rules winning the ties.
+    ///
     /// Preconditions:
     /// - our agent applies to this entry
     /// - filename is URL decoded
-    fn allowance(&self, filename: &str) -> bool {
+    fn allowance(&self, filename: &str) -> Option<(usize, bool)> {
+        let mut allowance: Option<(usize, bool)> = None;
+
         for line in &self.rulelines {
             if line.applies_to(filename) {
-                return line.allowance;
+                match allowance {
+                    Some((length, allowed))
+                        if length > line.path.len()
+                            || length == line.path.len() && (allowed || !line.allowance) => {}
+                    _ => allowance = Some((line.path.len(), line.allowance)),
+                }
             }
         }
-        true
+
+        allowance
     }
 
     /// Add to user agent list
@@ -188,6 +237,7 @@ impl RobotFileParser {
             disallow_all: false,
             allow_all: false,
             last_checked: 0i64,
+            sitemaps: vec![],
         }
         .into()
     }
@@ -214,15 +264,27 @@ impl RobotFileParser {
     pub async fn read(&mut self, client: &Client, url: &str) {
         self.modified();
 
-        let request = client.get(&string_concat!(url, "robots.txt"));
+        let request = crate::utils::send_request(
+            &string_concat!(url, "robots.txt"),
+            client,
+            Default::default(),
+        );
 
-        let res = match request.send().await {
+        let res = match request.await {
             Ok(res) => res,
             Err(_) => {
                 return;
             }
         };
         let status = res.status();
+        match status {
+            StatusCode::OK => self.from_response(res).await,
+            status => self.from_status(status, ""),
+        }
+    }
+
+    /// Feeds the status and the text of the robots.txt response to the parser.
+    pub fn from_status(&mut self, status: StatusCode, text: &str) {
         match status {
             StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN => {
                 self.disallow_all = true;
@@ -233,14 +295,20 @@ impl RobotFileParser {
             {
                 self.allow_all = true;
             }
-            StatusCode::OK => self.from_response(res).await,
+            StatusCode::OK => {
+                let lines: Vec<&str> = text.split('\n').collect();
+                self.parse(&lines);
+            }
             _ => (),
         }
     }
 
     /// Reads the HTTP response and feeds it to the parser.
     pub async fn from_response(&mut self, response: Response) {
-        let buf = response.text().await.unwrap();
+        let buf = crate::utils::get_body_bytes(response)
+            .await
+            .unwrap_or_default();
+        let buf = String::from_utf8_lossy(&buf);
         let lines: Vec<&str> = buf.split('\n').collect();
         self.parse(&lines);
     }
@@ -337,6 +405,12 @@ impl RobotFileParser {
                         }
                     }
                     ref x if x.to_lowercase() == "sitemap" => {
+                        // the sitemaps apply to the whole file outside of the entries
+                        let sitemap = parts[1].trim();
+
+                        if !sitemap.is_empty() && !self.sitemaps.iter().any(|s| s == sitemap) {
+                            self.sitemaps.push(sitemap.into());
+                        }
                         if state != 0 {
                             state = 2;
                         }
@@ -364,6 +438,11 @@ impl RobotFileParser {
         }
     }
 
+    /// Returns the urls of the `Sitemap` directives of the robots.txt.
+    pub fn get_sitemaps(&self) -> &[String] {
+        &self.sitemaps
+    }
+
     /// Using the parsed robots.txt decide if useragent can fetch url
     pub fn can_fetch<T: AsRef<str>>(&self, useragent: T, url: &str) -> bool {
         use percent_encoding::percent_decode;
@@ -383,61 +462,68 @@ impl RobotFileParser {
         if self.last_checked == 0 {
             return false;
         }
-        // search for given user agent matches
-        // the first match counts
+        // the rules match the path and the query of the absolute urls
+        let path = match url::Url::parse(url.trim()) {
+            Ok(url) => match url.query() {
+                Some(query) => string_concat!(url.path(), "?", query),
+                _ => url.path().into(),
+            },
+            _ => url.trim().into(),
+        };
         let decoded_url =
-            String::from_utf8(percent_decode(url.trim().as_bytes()).collect()).unwrap_or_default();
+            String::from_utf8(percent_decode(path.as_bytes()).collect()).unwrap_or_default();
 
         let url_str = match decoded_url {
             ref u if !u.is_empty() => u,
             _ => "/",
         };
 
-        for entry in &self.entries {
-            if entry.applies_to(useragent) {
-                return entry.allowance(&url_str);
+        // the longest rule of the groups of the user agent wins
+        let mut allowance: Option<(usize, bool)> = None;
+
+        for entry in self.get_entries(useragent) {
+            match (allowance, entry.allowance(&url_str)) {
+                (Some((length, allowed)), Some((entry_length, entry_allowed)))
+                    if length > entry_length
+                        || length == entry_length && (allowed || !entry_allowed) => {}
+                (_, Some(entry_allowance)) => allowance = Some(entry_allowance),
+                _ => (),
             }
         }
 
-        // try the default entry last
-        let default_entry = &self.default_entry;
+        // agent not found or no rule matching ==> access granted
+        allowance.map_or(true, |(_, allowed)| allowed)
+    }
 
-        if !default_entry.is_empty() {
-            return default_entry.allowance(&url_str);
+    /// Get the groups of the user agent merged per RFC 9309 defaulting to the `*` group.
+    fn get_entries(&self, useragent: &str) -> Vec<&Entry> {
+        let entries: Vec<&Entry> = self
+            .entries
+            .iter()
+            .filter(|entry| entry.applies_to(useragent))
+            .collect();
+
+        if entries.is_empty() && !self.default_entry.is_empty() {
+            vec![&self.default_entry]
+        } else {
+            entries
         }
-        // agent not found ==> access granted
-        true
     }
 
-    /// Returns the crawl delay for this user agent as a `Duration`, or None if no crawl delay is defined.
+    /// Returns the crawl delay of the groups matching this user agent as a `Duration` defaulting to
+    /// the `*` group, or None if no crawl delay is defined.
     pub fn get_crawl_delay(&self, useragent: &Option<Box<CompactString>>) -> Option<Duration> {
         if self.last_checked == 0 {
             None
         } else {
-            let useragent = useragent.as_ref();
-            let crawl_delay: Option<Duration> = match useragent {
-                Some(ua) => {
-                    for entry in &self.entries {
-                        if entry.applies_to(ua) {
-                            return entry.get_crawl_delay();
-                        }
-                    }
-                    None
-                }
-                _ => None,
+            let useragent = match useragent.as_ref() {
+                Some(ua) => ua.as_str(),
+                _ => "*",
             };
 
-            if crawl_delay.is_some() {
-                crawl_delay
-            } else {
-                let default_entry = &self.default_entry;
-
-                if !default_entry.is_empty() {
-                    return default_entry.get_crawl_delay();
-                }
-
-                None
-            }
+            self.get_entries(useragent)
+                .iter()
+                .find_map(|entry| entry.get_crawl_delay())
         }
     }
 
@@ -1,13 +1,26 @@
 #[cfg(not(feature = "decentralized"))]
-use crate::packages::scraper::Html;
+use crate::packages::scraper::{Html, Selector};
+use crate::configuration::{ContentTypes, RetryPolicy};
+use crate::features::bandwidth::BandwidthLimiter;
+use crate::features::challenge::Challenge;
+use crate::features::graph::LinkEdge;
+use crate::features::middleware::CrawlMiddleware;
+#[cfg(feature = "open_graph")]
+use crate::features::open_graph::SocialMetadata;
+#[cfg(feature = "structured_data")]
+use crate::features::structured_data::StructuredData;
+use crate::features::timings::RequestTimings;
+use crate::features::validators::{ValidatorStore, Validators};
 use crate::utils::log;
-use crate::utils::PageResponse;
+use crate::utils::{ContentEncoding, PageResponse, RequestErrorKind};
 use crate::CaseInsensitiveString;
 use bytes::Bytes;
 use compact_str::CompactString;
-use hashbrown::HashSet;
+use hashbrown::{HashMap, HashSet};
+use reqwest::header::HeaderMap;
 use reqwest::{Client, StatusCode};
 use smallvec::SmallVec;
+use std::sync::Arc;
 
 #[cfg(all(feature = "time", not(feature = "decentralized")))]
 use std::time::Duration;
@@ -41,6 +54,44 @@ pub struct Page {
     pub external_domains_caseless: Box<HashSet<CaseInsensitiveString>>,
     /// The final destination of the page if redirects were performed [Not implemented in the chrome feature].
     pub final_redirect_destination: Option<String>,
+    /// The urls of the redirects followed from the page url to the final destination. Requires `redirect_chain` to be enabled.
+    pub redirect_chain: Option<Vec<String>>,
+    /// The duration the server asked to wait before the next request with a 429 or 503 status.
+    pub retry_after: Option<std::time::Duration>,
+    /// Skip the anchors with a nofollow, sponsored, or ugc rel when gathering the links.
+    skip_rel_nofollow: bool,
+    /// The page is unchanged since the last crawl from a conditional request with the validators stored.
+    pub not_modified: bool,
+    /// The ETag and Last-Modified validators of the response to request the page conditionally on the next crawls.
+    pub validators: Option<Validators>,
+    /// The digest of the page content normalized to detect the changes between crawls. Requires `content_diff` to be enabled.
+    pub digest: Option<u64>,
+    /// The headers of the response [Not implemented in the chrome feature].
+    pub headers: Option<HeaderMap>,
+    /// The HTTP version of the response [Not implemented in the chrome feature].
+    pub http_version: Option<reqwest::Version>,
+    /// The class of the error of the request failed without a response.
+    pub error_kind: Option<RequestErrorKind>,
+    /// The retries of the request on transient failures.
+    pub retries: u8,
+    /// The content is truncated at the max response size.
+    pub truncated: bool,
+    /// The html is not gathered with the content type excluded.
+    pub content_type_excluded: bool,
+    /// The content encoding of the response decoded like gzip, brotli, or zstd [Not implemented in the chrome feature].
+    pub content_encoding: Option<ContentEncoding>,
+    /// The durations of the DNS lookup, the time to the first byte, and the download of the body of the request [Not implemented in the chrome and fs features].
+    pub timings: Option<RequestTimings>,
+    /// The charset of the html transcoded to UTF-8.
+    charset: &'static encoding_rs::Encoding,
+    /// The bot challenge of the page not solved. Requires the challenge callback to be set.
+    pub challenge: Option<Challenge>,
+    #[cfg(feature = "chrome")]
+    /// The screenshot of the page rendered. Requires `screenshot` to be set with the bytes attached.
+    pub screenshot: Option<Bytes>,
+    #[cfg(feature = "chrome")]
+    /// The PDF of the page rendered. Requires `pdf` to be set with the bytes attached.
+    pub pdf: Option<Bytes>,
     #[cfg(feature = "time")]
     /// The duration from start of parsing to end of gathering links.
     duration: Instant,
@@ -62,6 +113,90 @@ pub struct Page {
     pub external_domains_caseless: Box<HashSet<CaseInsensitiveString>>,
     /// The final destination of the page if redirects were performed [Unused].
     pub final_redirect_destination: Option<String>,
+    /// The duration the server asked to wait before the next request with a 429 or 503 status.
+    pub retry_after: Option<std::time::Duration>,
+    /// The page is unchanged since the last crawl [Unused].
+    pub not_modified: bool,
+    /// The validators of the response [Unused].
+    pub validators: Option<Validators>,
+    /// The digest of the page content [Unused].
+    pub digest: Option<u64>,
+    /// The headers of the response [Unused].
+    pub headers: Option<HeaderMap>,
+    /// The HTTP version of the response [Unused].
+    pub http_version: Option<reqwest::Version>,
+    /// The class of the error of the request failed without a response.
+    pub error_kind: Option<RequestErrorKind>,
+    /// The retries of the request on transient failures [Unused].
+    pub retries: u8,
+    /// The content is truncated at the max response size [Unused].
+    pub truncated: bool,
+    /// The html is not gathered with the content type excluded [Unused].
+    pub content_type_excluded: bool,
+    /// The content encoding of the response decoded [Unused].
+    pub content_encoding: Option<ContentEncoding>,
+    /// The durations of the phases of the request [Unused].
+    pub timings: Option<RequestTimings>,
+    /// The charset of the html transcoded to UTF-8.
+    charset: &'static encoding_rs::Encoding,
+    /// The bot challenge of the page not solved [Unused].
+    pub challenge: Option<Challenge>,
+}
+
+/// The directives of the robots meta tags of a page.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct MetaRobots {
+    /// The page should not be indexed.
+    pub noindex: bool,
+    /// The links of the page should not be followed.
+    pub nofollow: bool,
+}
+
+/// The SEO metadata of a page.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct SeoMetadata {
+    /// The text of the `<title>` with the whitespace collapsed.
+    pub title: Option<String>,
+    /// The content of the `<meta name="description">`.
+    pub description: Option<String>,
+    /// The texts of the `<h1>` headings with the whitespace collapsed.
+    pub h1: Vec<String>,
+    /// The absolute url of the `<link rel="canonical">`.
+    pub canonical: Option<Url>,
+    /// The directives of the `<meta name="robots">` tags.
+    pub meta_robots: MetaRobots,
+    /// The language and the absolute url of the `<link rel="alternate" hreflang>` links.
+    pub hreflang: Vec<(String, Url)>,
+}
+
+/// Get the directives of the `<meta name="robots">` tags of the html parsed.
+#[cfg(not(feature = "decentralized"))]
+fn get_meta_robots(html: &Html) -> MetaRobots {
+    let mut meta_robots = MetaRobots::default();
+
+    for node in html.tree.values() {
+        if let Some(element) = node.as_element() {
+            if element.name() == "meta"
+                && element
+                    .attr("name")
+                    .map_or(false, |name| name.eq_ignore_ascii_case("robots"))
+            {
+                for directive in element.attr("content").unwrap_or_default().split(',') {
+                    match directive.trim().to_ascii_lowercase().as_str() {
+                        "noindex" => meta_robots.noindex = true,
+                        "nofollow" => meta_robots.nofollow = true,
+                        "none" => {
+                            meta_robots.noindex = true;
+                            meta_robots.nofollow = true;
+                        }
+                        _ => (),
+                    }
+                }
+            }
+        }
+    }
+
+    meta_robots
 }
 
 lazy_static! {
@@ -147,9 +282,23 @@ pub fn get_page_selectors(
     }
 }
 
+/// Get the class of the error of the request failed without a response.
+fn get_error_kind(res: &PageResponse) -> Option<RequestErrorKind> {
+    match &res.error_for_status {
+        Some(Err(e)) => Some(RequestErrorKind::new(e)),
+        _ => None,
+    }
+}
+
 /// Instantiate a new page without scraping it (used for testing purposes).
 #[cfg(not(feature = "decentralized"))]
 pub fn build(url: &str, res: PageResponse) -> Page {
+    let error_kind = get_error_kind(&res);
+    let charset = crate::utils::detect_charset(
+        res.headers.as_ref(),
+        res.content.as_deref().unwrap_or_default(),
+    );
+
     Page {
         html: if res.content.is_some() {
             res.content
@@ -162,6 +311,26 @@ pub fn build(url: &str, res: PageResponse) -> Page {
         duration: Instant::now(),
         external_domains_caseless: Default::default(),
         final_redirect_destination: res.final_url,
+        redirect_chain: res.redirect_chain,
+        retry_after: res.retry_after,
+        skip_rel_nofollow: false,
+        not_modified: res.status_code == StatusCode::NOT_MODIFIED,
+        validators: res.validators,
+        digest: None,
+        headers: res.headers,
+        http_version: res.http_version,
+        error_kind,
+        retries: res.retries,
+        truncated: res.truncated,
+        content_type_excluded: res.content_type_excluded,
+        content_encoding: res.content_encoding,
+        timings: res.timings,
+        charset,
+        challenge: None,
+        #[cfg(feature = "chrome")]
+        screenshot: res.screenshot,
+        #[cfg(feature = "chrome")]
+        pdf: res.pdf,
         status_code: res.status_code,
         error_status: match res.error_for_status {
             Some(e) => match e {
@@ -176,6 +345,9 @@ pub fn build(url: &str, res: PageResponse) -> Page {
 /// Instantiate a new page without scraping it (used for testing purposes).
 #[cfg(feature = "decentralized")]
 pub fn build(_: &str, res: PageResponse) -> Page {
+    let error_kind = get_error_kind(&res);
+    let charset = crate::utils::detect_charset(None, res.content.as_deref().unwrap_or_default());
+
     Page {
         html: if res.content.is_some() {
             res.content
@@ -185,6 +357,20 @@ pub fn build(_: &str, res: PageResponse) -> Page {
         links: Default::default(),
         external_domains_caseless: Default::default(),
         final_redirect_destination: res.final_url,
+        retry_after: res.retry_after,
+        not_modified: false,
+        validators: None,
+        digest: None,
+        headers: None,
+        http_version: None,
+        error_kind,
+        retries: 0,
+        truncated: false,
+        content_type_excluded: false,
+        content_encoding: None,
+        timings: None,
+        charset,
+        challenge: None,
         status_code: res.status_code,
         error_status: match res.error_for_status {
             Some(e) => match e {
@@ -203,6 +389,67 @@ impl Page {
         build(url, page_resource)
     }
 
+    /// Instantiate a new page retrying transient failures and gather the html repro of standard fetch_page_html.
+    pub async fn new_page_retry(url: &str, client: &Client, retry: &RetryPolicy) -> Self {
+        let page_resource = crate::utils::fetch_with_retry(retry, || {
+            crate::utils::fetch_page_html_raw(&url, &client)
+        })
+        .await;
+        build(url, page_resource)
+    }
+
+    /// Instantiate a new page retrying transient failures requesting the page conditionally with the
+    /// validators stored for the url and gather the html repro of standard fetch_page_html.
+    pub async fn new_page_validated(
+        url: &str,
+        client: &Client,
+        retry: &RetryPolicy,
+        validator_store: &dyn ValidatorStore,
+    ) -> Self {
+        Self::new_page_middleware(
+            url,
+            client,
+            retry,
+            Some(validator_store),
+            &[],
+            0,
+            None,
+            None,
+        )
+        .await
+    }
+
+    /// Instantiate a new page retrying transient failures running the middlewares around the
+    /// request conditional with the validators stored for the url and gather the html repro of
+    /// standard fetch_page_html truncated at the max size. Use 0 to gather all the html. The html
+    /// of the content types excluded is not gathered. The html is downloaded under the bandwidth
+    /// limit.
+    pub async fn new_page_middleware(
+        url: &str,
+        client: &Client,
+        retry: &RetryPolicy,
+        validator_store: Option<&dyn ValidatorStore>,
+        middlewares: &[Arc<dyn CrawlMiddleware>],
+        max_size: usize,
+        content_types: Option<&ContentTypes>,
+        bandwidth_limit: Option<&BandwidthLimiter>,
+    ) -> Self {
+        let validators = validator_store.and_then(|store| store.get(url));
+        let page_resource = crate::utils::fetch_with_retry(retry, || {
+            crate::utils::fetch_page_html_middleware(
+                &url,
+                &client,
+                validators.as_ref(),
+                middlewares,
+                max_size,
+                content_types,
+                bandwidth_limit,
+            )
+        })
+        .await;
+        build(url, page_resource)
+    }
+
     /// Instantiate a new page and gather the html.
     #[cfg(all(not(feature = "decentralized"), not(feature = "chrome")))]
     pub async fn new(url: &str, client: &Client) -> Self {
@@ -217,6 +464,51 @@ impl Page {
         build(url, page_resource)
     }
 
+    #[cfg(all(not(feature = "decentralized"), feature = "chrome"))]
+    /// Instantiate a new page and gather the html after the wait condition capturing the screenshot
+    /// and the pdf.
+    pub async fn new_rendered(
+        url: &str,
+        client: &Client,
+        page: &chromiumoxide::Page,
+        wait_for: &crate::configuration::WaitFor,
+        screenshot: &Option<Box<crate::configuration::ScreenshotParams>>,
+        pdf: &Option<Box<crate::configuration::PdfParams>>,
+    ) -> Self {
+        let page_resource =
+            crate::utils::fetch_page_html_rendered(&url, &client, &page, wait_for, screenshot, pdf)
+                .await;
+        build(url, page_resource)
+    }
+
+    /// Instantiate a new page retrying transient failures and gather the html.
+    #[cfg(all(not(feature = "decentralized"), not(feature = "chrome")))]
+    pub async fn new_retry(url: &str, client: &Client, retry: &RetryPolicy) -> Self {
+        let page_resource = crate::utils::fetch_with_retry(retry, || {
+            crate::utils::fetch_page_html(&url, &client)
+        })
+        .await;
+        build(url, page_resource)
+    }
+
+    #[cfg(all(not(feature = "decentralized"), feature = "chrome"))]
+    /// Instantiate a new page retrying transient failures and gather the html after the wait condition.
+    pub async fn new_retry(
+        url: &str,
+        client: &Client,
+        page: &chromiumoxide::Page,
+        wait_for: &crate::configuration::WaitFor,
+        screenshot: &Option<Box<crate::configuration::ScreenshotParams>>,
+        pdf: &Option<Box<crate::configuration::PdfParams>>,
+        retry: &RetryPolicy,
+    ) -> Self {
+        let page_resource = crate::utils::fetch_with_retry(retry, || {
+            crate::utils::fetch_page_html_rendered(&url, &client, &page, wait_for, screenshot, pdf)
+        })
+        .await;
+        build(url, page_resource)
+    }
+
     /// Instantiate a new page and gather the links.
     #[cfg(feature = "decentralized")]
     pub async fn new(url: &str, client: &Client) -> Self {
@@ -238,6 +530,20 @@ impl Page {
             links,
             external_domains_caseless: Default::default(),
             final_redirect_destination: Default::default(),
+            retry_after: Default::default(),
+            not_modified: false,
+            validators: None,
+            digest: None,
+            headers: None,
+            http_version: None,
+            error_kind: None,
+            retries: 0,
+            truncated: false,
+            content_type_excluded: false,
+            content_encoding: None,
+            timings: None,
+            charset: encoding_rs::UTF_8,
+            challenge: None,
             status_code: Default::default(),
             error_status: Default::default(),
         }
@@ -248,6 +554,14 @@ impl Page {
         self.html.is_none()
     }
 
+    /// Page request failed from a network or server error or a bot challenge not solved.
+    pub fn is_failed(&self) -> bool {
+        self.status_code.is_server_error()
+            || self.status_code == StatusCode::TOO_MANY_REQUESTS
+            || self.error_status.is_some()
+            || self.challenge.is_some()
+    }
+
     /// Url getter for page.
     #[cfg(not(feature = "decentralized"))]
     pub fn get_url(&self) -> &str {
@@ -268,6 +582,29 @@ impl Page {
         self.external_domains_caseless = external_domains_caseless;
     }
 
+    /// Skip the anchors with a nofollow, sponsored, or ugc rel when gathering the links.
+    #[cfg(not(feature = "decentralized"))]
+    pub fn set_skip_rel_nofollow(&mut self, skip_rel_nofollow: bool) {
+        self.skip_rel_nofollow = skip_rel_nofollow;
+    }
+
+    /// Skip the anchors with a nofollow, sponsored, or ugc rel when gathering the links [Unused].
+    #[cfg(feature = "decentralized")]
+    pub fn set_skip_rel_nofollow(&mut self, _: bool) {}
+
+    /// The anchor is skipped from the links by its nofollow, sponsored, or ugc rel.
+    #[cfg(not(feature = "decentralized"))]
+    fn is_rel_skipped(&self, rel: Option<&str>) -> bool {
+        self.skip_rel_nofollow
+            && rel.map_or(false, |rel| {
+                rel.split_ascii_whitespace().any(|rel| {
+                    rel.eq_ignore_ascii_case("nofollow")
+                        || rel.eq_ignore_ascii_case("sponsored")
+                        || rel.eq_ignore_ascii_case("ugc")
+                })
+            })
+    }
+
     /// Parsed URL getter for page.
     #[cfg(not(feature = "decentralized"))]
     pub fn get_url_parsed(&self) -> &Url {
@@ -280,7 +617,7 @@ impl Page {
         ""
     }
 
-    /// Html getter for bytes on the page.
+    /// Html getter for the raw bytes of the response body on the page.
     pub fn get_bytes(&self) -> Option<&Bytes> {
         match self.html.as_ref() {
             Some(html) => Some(html),
@@ -288,14 +625,20 @@ impl Page {
         }
     }
 
-    /// Html getter for bytes on the page as string.
+    /// Html getter for bytes on the page as string transcoded to UTF-8 from the charset of the page.
     pub fn get_html(&self) -> String {
         match self.html.as_ref() {
-            Some(html) => String::from_utf8_lossy(&html).to_string(),
+            Some(html) => self.charset.decode(html).0.into_owned(),
             _ => Default::default(),
         }
     }
 
+    /// Get the name of the charset of the html like `Shift_JIS` detected from the byte order mark,
+    /// the Content-Type header, or the `<meta charset>` of the page.
+    pub fn get_charset(&self) -> &'static str {
+        self.charset.name()
+    }
+
     /// Html getter for page to u8.
     pub fn get_html_bytes_u8(&self) -> &[u8] {
         match self.html.as_deref() {
@@ -304,6 +647,447 @@ impl Page {
         }
     }
 
+    /// Get the directives of the `<meta name="robots">` tags of the page.
+    #[cfg(not(feature = "decentralized"))]
+    pub fn get_meta_robots(&self) -> MetaRobots {
+        if self.html.is_none() {
+            return Default::default();
+        }
+
+        get_meta_robots(&Html::parse_document(&self.get_html()))
+    }
+
+    /// Get the absolute url of the `<link rel="canonical">` of the page.
+    #[cfg(not(feature = "decentralized"))]
+    pub fn get_canonical_url(&self) -> Option<Url> {
+        if self.html.is_none() {
+            return None;
+        }
+
+        self.get_canonical_url_parsed(&Html::parse_document(&self.get_html()))
+    }
+
+    /// Get the absolute url of the `<link rel="canonical">` of the html parsed.
+    #[cfg(not(feature = "decentralized"))]
+    fn get_canonical_url_parsed(&self, html: &Html) -> Option<Url> {
+        for node in html.tree.values() {
+            if let Some(element) = node.as_element() {
+                if element.name() == "link"
+                    && element.attr("rel").map_or(false, |rel| {
+                        rel.split_ascii_whitespace()
+                            .any(|rel| rel.eq_ignore_ascii_case("canonical"))
+                    })
+                {
+                    match element.attr("href") {
+                        Some(href) if !href.trim().is_empty() => {
+                            return Some(self.abs_path(href.trim()));
+                        }
+                        _ => (),
+                    }
+                }
+            }
+        }
+
+        None
+    }
+
+    /// Get the title, description, h1s, canonical url, robots meta tags, and hreflang alternates of
+    /// the page.
+    #[cfg(not(feature = "decentralized"))]
+    pub fn get_seo_metadata(&self) -> SeoMetadata {
+        if self.html.is_none() {
+            return Default::default();
+        }
+
+        let html = Html::parse_document(&self.get_html());
+        let select = |selector: &str| match Selector::parse(selector) {
+            Ok(selector) => html
+                .select(&selector)
+                .map(|element| {
+                    element
+                        .text()
+                        .flat_map(|text| text.split_whitespace())
+                        .collect::<Vec<_>>()
+                        .join(" ")
+                })
+                .collect(),
+            _ => Vec::new(),
+        };
+        let mut seo_metadata = SeoMetadata {
+            title: select("title").into_iter().next(),
+            h1: select("h1"),
+            canonical: self.get_canonical_url_parsed(&html),
+            meta_robots: get_meta_robots(&html),
+            ..Default::default()
+        };
+
+        for node in html.tree.values() {
+            if let Some(element) = node.as_element() {
+                match element.name() {
+                    "meta"
+                        if seo_metadata.description.is_none()
+                            && element
+                                .attr("name")
+                                .map_or(false, |name| name.eq_ignore_ascii_case("description")) =>
+                    {
+                        seo_metadata.description = element
+                            .attr("content")
+                            .map(|content| content.trim().to_string());
+                    }
+                    "link"
+                        if element.attr("rel").map_or(false, |rel| {
+                            rel.split_ascii_whitespace()
+                                .any(|rel| rel.eq_ignore_ascii_case("alternate"))
+                        }) =>
+                    {
+                        match (element.attr("hreflang"), element.attr("href")) {
+                            (Some(hreflang), Some(href)) if !href.trim().is_empty() => {
+                                seo_metadata
+                                    .hreflang
+                                    .push((hreflang.trim().into(), self.abs_path(href.trim())));
+                            }
+                            _ => (),
+                        }
+                    }
+                    _ => (),
+                }
+            }
+        }
+
+        seo_metadata
+    }
+
+    /// Get the absolute url of the canonical link of the page [Unused].
+    #[cfg(feature = "decentralized")]
+    pub fn get_canonical_url(&self) -> Option<Url> {
+        None
+    }
+
+    /// Get the SEO metadata of the page [Unused].
+    #[cfg(feature = "decentralized")]
+    pub fn get_seo_metadata(&self) -> SeoMetadata {
+        Default::default()
+    }
+
+    /// Get the JSON-LD, microdata, and RDFa structured data of the page.
+    #[cfg(all(feature = "structured_data", not(feature = "decentralized")))]
+    pub fn get_structured_data(&self) -> StructuredData {
+        if self.html.is_none() {
+            return Default::default();
+        }
+
+        StructuredData::new(&Html::parse_document(&self.get_html()), &self.base)
+    }
+
+    /// Get the JSON-LD, microdata, and RDFa structured data of the page [Unused].
+    #[cfg(all(feature = "structured_data", feature = "decentralized"))]
+    pub fn get_structured_data(&self) -> StructuredData {
+        Default::default()
+    }
+
+    /// Get the Open Graph and Twitter Card metadata of the page.
+    #[cfg(all(feature = "open_graph", not(feature = "decentralized")))]
+    pub fn get_social_metadata(&self) -> SocialMetadata {
+        if self.html.is_none() {
+            return Default::default();
+        }
+
+        SocialMetadata::new(&Html::parse_document(&self.get_html()), &self.base)
+    }
+
+    /// Get the Open Graph and Twitter Card metadata of the page [Unused].
+    #[cfg(all(feature = "open_graph", feature = "decentralized"))]
+    pub fn get_social_metadata(&self) -> SocialMetadata {
+        Default::default()
+    }
+
+    /// Get the ISO 639-1 code of the language of the page like `en` detected from its text or the
+    /// `lang` of the `<html>` tag.
+    #[cfg(all(feature = "language", not(feature = "decentralized")))]
+    pub fn get_language(&self) -> Option<CompactString> {
+        if self.html.is_none() {
+            return None;
+        }
+
+        crate::features::language::detect_language(&Html::parse_document(&self.get_html()))
+    }
+
+    /// Get the language of the page [Unused].
+    #[cfg(all(feature = "language", feature = "decentralized"))]
+    pub fn get_language(&self) -> Option<CompactString> {
+        None
+    }
+
+    /// Get the main content of the page with its title and its text without the boilerplate like
+    /// the navigation, the footers, and the ads.
+    #[cfg(all(feature = "text_extraction", not(feature = "decentralized")))]
+    pub fn get_article(&self) -> crate::features::text_extraction::Article {
+        if self.html.is_none() {
+            return Default::default();
+        }
+
+        crate::features::text_extraction::Article::new(&Html::parse_document(&self.get_html()))
+    }
+
+    /// Get the main content of the page [Unused].
+    #[cfg(all(feature = "text_extraction", feature = "decentralized"))]
+    pub fn get_article(&self) -> crate::features::text_extraction::Article {
+        Default::default()
+    }
+
+    /// Get the markdown of the page with its headings, lists, links, and code blocks. The links
+    /// and the images are absolute urls.
+    #[cfg(all(feature = "markdown", not(feature = "decentralized")))]
+    pub fn get_markdown(&self) -> String {
+        if self.html.is_none() {
+            return Default::default();
+        }
+
+        crate::features::markdown::html_to_markdown(
+            &Html::parse_document(&self.get_html()),
+            &self.base,
+        )
+    }
+
+    /// Get the markdown of the page [Unused].
+    #[cfg(all(feature = "markdown", feature = "decentralized"))]
+    pub fn get_markdown(&self) -> String {
+        Default::default()
+    }
+
+    /// Get the fingerprints of the text of the page to detect its duplicates and near duplicates.
+    /// Returns `None` when the page has no text.
+    #[cfg(all(feature = "dedup", not(feature = "decentralized")))]
+    pub fn get_fingerprint(&self) -> Option<crate::features::dedup::Fingerprint> {
+        if self.html.is_none() {
+            return None;
+        }
+
+        crate::features::dedup::Fingerprint::from_html(&Html::parse_document(&self.get_html()))
+    }
+
+    /// Get the fingerprints of the text of the page [Unused].
+    #[cfg(all(feature = "dedup", feature = "decentralized"))]
+    pub fn get_fingerprint(&self) -> Option<crate::features::dedup::Fingerprint> {
+        None
+    }
+
+    /// Get the absolute urls of the RSS and Atom feeds of the `<link rel="alternate">` of the page.
+    #[cfg(all(feature = "feeds", not(feature = "decentralized")))]
+    pub fn get_feed_urls(&self) -> Vec<Url> {
+        use crate::features::feeds::FEED_TYPES;
+        let mut feed_urls: Vec<Url> = Vec::new();
+
+        if self.html.is_none() {
+            return feed_urls;
+        }
+
+        let html = Html::parse_document(&self.get_html());
+
+        for node in html.tree.values() {
+            match node.as_element() {
+                Some(element)
+                    if element.name() == "link"
+                        && element.attr("rel").map_or(false, |rel| {
+                            rel.split_ascii_whitespace()
+                                .any(|rel| rel.eq_ignore_ascii_case("alternate"))
+                        })
+                        && element.attr("type").map_or(false, |kind| {
+                            FEED_TYPES
+                                .iter()
+                                .any(|t| kind.trim().eq_ignore_ascii_case(t))
+                        }) =>
+                {
+                    match element.attr("href") {
+                        Some(href) if !href.trim().is_empty() => {
+                            let feed_url = self.abs_path(href.trim());
+
+                            if !feed_urls.contains(&feed_url) {
+                                feed_urls.push(feed_url);
+                            }
+                        }
+                        _ => (),
+                    }
+                }
+                _ => (),
+            }
+        }
+
+        feed_urls
+    }
+
+    /// Get the absolute urls of the RSS and Atom feeds of the page [Unused].
+    #[cfg(all(feature = "feeds", feature = "decentralized"))]
+    pub fn get_feed_urls(&self) -> Vec<Url> {
+        Default::default()
+    }
+
+    /// Get the text of the first anchor with text per absolute url of the page skipping the
+    /// anchors skipped from the links by their rel.
+    #[cfg(not(feature = "decentralized"))]
+    pub fn get_anchor_texts(&self) -> HashMap<CaseInsensitiveString, String> {
+        let mut anchor_texts = HashMap::new();
+
+        if self.html.is_none() {
+            return anchor_texts;
+        }
+
+        let html = Html::parse_document(&self.get_html());
+
+        if let Ok(selector) = Selector::parse("a[href]") {
+            for element in html.select(&selector) {
+                if self.is_rel_skipped(element.attr("rel")) {
+                    continue;
+                }
+
+                match element.attr("href") {
+                    Some(href) => {
+                        let text = element
+                            .text()
+                            .flat_map(|text| text.split_whitespace())
+                            .collect::<Vec<_>>()
+                            .join(" ");
+
+                        if !text.is_empty() {
+                            anchor_texts
+                                .entry(self.abs_path(href).as_str().into())
+                                .or_insert(text);
+                        }
+                    }
+                    _ => (),
+                }
+            }
+        }
+
+        anchor_texts
+    }
+
+    /// Get the text of the anchors per absolute url of the page [Unused].
+    #[cfg(feature = "decentralized")]
+    pub fn get_anchor_texts(&self) -> HashMap<CaseInsensitiveString, String> {
+        Default::default()
+    }
+
+    /// Get the edges from the page to the absolute http urls of all its anchors with their text
+    /// and rel.
+    #[cfg(not(feature = "decentralized"))]
+    pub fn get_link_edges(&self) -> Vec<LinkEdge> {
+        let mut edges = Vec::new();
+
+        if self.html.is_none() {
+            return edges;
+        }
+
+        let html = Html::parse_document(&self.get_html());
+
+        if let Ok(selector) = Selector::parse("a[href]") {
+            for element in html.select(&selector) {
+                match element.attr("href") {
+                    Some(href) => {
+                        let target = self.abs_path(href);
+
+                        if target.scheme() == "http" || target.scheme() == "https" {
+                            edges.push(LinkEdge {
+                                source: self.url.clone(),
+                                target: target.into(),
+                                anchor_text: element
+                                    .text()
+                                    .flat_map(|text| text.split_whitespace())
+                                    .collect::<Vec<_>>()
+                                    .join(" "),
+                                rel: element.attr("rel").unwrap_or_default().into(),
+                            });
+                        }
+                    }
+                    _ => (),
+                }
+            }
+        }
+
+        edges
+    }
+
+    /// Get the absolute urls of the assets of the page from the sources of the media elements and
+    /// the anchors to a resource not html.
+    #[cfg(not(feature = "decentralized"))]
+    pub fn get_asset_urls(&self) -> Vec<Url> {
+        let mut asset_urls: Vec<Url> = Vec::new();
+
+        if self.html.is_none() {
+            return asset_urls;
+        }
+
+        let html = Html::parse_document(&self.get_html());
+
+        for node in html.tree.values() {
+            let source = match node.as_element() {
+                Some(element) if element.name() == "a" => match element.attr("href") {
+                    Some(href) => {
+                        let url = self.abs_path(href.trim());
+
+                        match crate::features::assets::get_extension(&url) {
+                            Some(extension)
+                                if !ONLY_RESOURCES
+                                    .contains::<CaseInsensitiveString>(&extension.into()) =>
+                            {
+                                Some(url)
+                            }
+                            _ => None,
+                        }
+                    }
+                    _ => None,
+                },
+                Some(element)
+                    if matches!(
+                        element.name(),
+                        "img" | "source" | "video" | "audio" | "embed" | "track"
+                    ) =>
+                {
+                    element
+                        .attr("src")
+                        .filter(|src| !src.trim().is_empty() && !src.starts_with("data:"))
+                        .map(|src| self.abs_path(src.trim()))
+                }
+                Some(element) if element.name() == "object" => element
+                    .attr("data")
+                    .filter(|data| !data.trim().is_empty())
+                    .map(|data| self.abs_path(data.trim())),
+                _ => None,
+            };
+
+            match source {
+                Some(mut url) => {
+                    url.set_fragment(None);
+
+                    if !asset_urls.contains(&url) {
+                        asset_urls.push(url);
+                    }
+                }
+                _ => (),
+            }
+        }
+
+        asset_urls
+    }
+
+    /// Get the absolute urls of the assets of the page [Unused].
+    #[cfg(feature = "decentralized")]
+    pub fn get_asset_urls(&self) -> Vec<Url> {
+        Default::default()
+    }
+
+    /// Get the edges from the page to the urls of its anchors [Unused].
+    #[cfg(feature = "decentralized")]
+    pub fn get_link_edges(&self) -> Vec<LinkEdge> {
+        Default::default()
+    }
+
+    /// Get the directives of the robots meta tags of the page [Unused].
+    #[cfg(feature = "decentralized")]
+    pub fn get_meta_robots(&self) -> MetaRobots {
+        Default::default()
+    }
+
     /// Get the elasped duration of the page since scraped.
     #[cfg(all(feature = "time", not(feature = "decentralized")))]
     pub fn get_duration_elasped(&self) -> Duration {
@@ -334,7 +1118,7 @@ impl Page {
 
         while let Some(node) = stream.next().await {
             if let Some(element) = node.as_element() {
-                if element.name() == "a" {
+                if element.name() == "a" && !self.is_rel_skipped(element.attr("rel")) {
                     match element.attr("href") {
                         Some(href) => {
                             let mut abs = self.abs_path(href);
@@ -527,7 +1311,7 @@ impl Page {
                             _ => (),
                         }
                     }
-                    if element_name == "a" {
+                    if element_name == "a" && !self.is_rel_skipped(element.attr("rel")) {
                         match element.attr("href") {
                             Some(href) => {
                                 let mut abs = self.abs_path(href);
@@ -608,6 +1392,10 @@ impl Page {
                     "href"
                 };
 
+                if element_name == "a" && self.is_rel_skipped(element.attr("rel")) {
+                    continue;
+                }
+
                 match element.attr(ele_attribute) {
                     Some(href) => {
                         let mut abs = self.abs_path(href);
@@ -779,3 +1567,450 @@ async fn test_duration() {
         duration_elasped,
     );
 }
+
+#[cfg(not(feature = "decentralized"))]
+#[tokio::test]
+async fn test_meta_robots() {
+    let html = r#"<html><head><meta name="Robots" content="noindex, NOFOLLOW"></head>
+        <body><a href="/about/">About</a></body></html>"#;
+    let link_result = "https://choosealicense.com/";
+    let page = build(
+        link_result,
+        PageResponse {
+            content: Some(Bytes::from(html)),
+            ..Default::default()
+        },
+    );
+
+    assert_eq!(
+        page.get_meta_robots(),
+        MetaRobots {
+            noindex: true,
+            nofollow: true
+        }
+    );
+
+    let page = build(link_result, Default::default());
+
+    assert_eq!(page.get_meta_robots(), MetaRobots::default());
+}
+
+#[cfg(not(feature = "decentralized"))]
+#[tokio::test]
+async fn test_canonical_url() {
+    let html = r#"<html><head><link rel="canonical" href="/licenses/"></head></html>"#;
+    let page = build(
+        "https://choosealicense.com/licenses/?page=1",
+        PageResponse {
+            content: Some(Bytes::from(html)),
+            ..Default::default()
+        },
+    );
+
+    assert_eq!(
+        page.get_canonical_url(),
+        Url::parse("https://choosealicense.com/licenses/").ok()
+    );
+}
+
+#[cfg(all(
+    not(feature = "decentralized"),
+    not(feature = "full_resources"),
+    not(feature = "js")
+))]
+#[tokio::test]
+async fn test_links_rel_nofollow() {
+    let html = r#"<html><body><a href="/about/">About</a>
+        <a href="/ads/" rel="noopener Sponsored">Ads</a>
+        <a href="/comments/" rel="ugc">Comments</a></body></html>"#;
+    let link_result = "https://choosealicense.com/";
+    let mut page = build(
+        link_result,
+        PageResponse {
+            content: Some(Bytes::from(html)),
+            ..Default::default()
+        },
+    );
+    let selector = get_page_selectors(&link_result, false, false).unwrap();
+
+    assert_eq!(page.links(&selector).await.len(), 3);
+
+    page.set_skip_rel_nofollow(true);
+    let links = page.links(&selector).await;
+
+    assert_eq!(links.len(), 1);
+    assert!(links.contains::<CaseInsensitiveString>(&"https://choosealicense.com/about/".into()));
+}
+
+#[cfg(not(feature = "decentralized"))]
+#[tokio::test]
+async fn test_raw_response() {
+    let mut headers = HeaderMap::new();
+    headers.insert(
+        reqwest::header::CONTENT_TYPE,
+        reqwest::header::HeaderValue::from_static("text/html; charset=latin1"),
+    );
+    let page = build(
+        "https://choosealicense.com/licenses",
+        PageResponse {
+            content: Some(Bytes::from_static(b"<p>caf\xe9</p>")),
+            final_url: Some("https://choosealicense.com/licenses/".into()),
+            headers: Some(headers),
+            http_version: Some(reqwest::Version::HTTP_2),
+            ..Default::default()
+        },
+    );
+
+    assert_eq!(page.get_html_bytes_u8(), b"<p>caf\xe9</p>");
+    assert_eq!(page.get_url_final(), "https://choosealicense.com/licenses/");
+    assert_eq!(page.http_version, Some(reqwest::Version::HTTP_2));
+    assert_eq!(
+        page.headers
+            .as_ref()
+            .and_then(|headers| headers.get(reqwest::header::CONTENT_TYPE)),
+        Some(&reqwest::header::HeaderValue::from_static(
+            "text/html; charset=latin1"
+        ))
+    );
+}
+
+#[cfg(not(feature = "decentralized"))]
+#[test]
+fn test_charset() {
+    let mut headers = HeaderMap::new();
+    headers.insert(
+        reqwest::header::CONTENT_TYPE,
+        reqwest::header::HeaderValue::from_static("text/html; charset=latin1"),
+    );
+    let page = build(
+        "https://choosealicense.com/licenses",
+        PageResponse {
+            content: Some(Bytes::from_static(b"<p>caf\xe9</p>")),
+            headers: Some(headers),
+            ..Default::default()
+        },
+    );
+
+    assert_eq!(page.get_charset(), "windows-1252");
+    assert_eq!(page.get_html(), "<p>caf\u{e9}</p>");
+
+    let page = build(
+        "https://choosealicense.com/",
+        PageResponse {
+            content: Some(Bytes::from_static(
+                b"<html><head><meta charset=\"Shift_JIS\"></head><body><a href=\"/\x93\xfa\x96\x7b/\">\x93\xfa\x96\x7b</a></body></html>",
+            )),
+            ..Default::default()
+        },
+    );
+    let edges = page.get_link_edges();
+
+    assert_eq!(page.get_charset(), "Shift_JIS");
+    assert_eq!(
+        edges[0].target,
+        "https://choosealicense.com/%E6%97%A5%E6%9C%AC/"
+    );
+    assert_eq!(edges[0].anchor_text, "\u{65e5}\u{672c}");
+
+    let page = build(
+        "https://choosealicense.com/",
+        PageResponse {
+            content: Some(Bytes::from_static(
+                b"<meta http-equiv=\"Content-Type\" content=\"text/html; charset=windows-1251\"><p>\xcf\xf0\xe8\xe2\xe5\xf2</p>",
+            )),
+            ..Default::default()
+        },
+    );
+
+    assert_eq!(page.get_charset(), "windows-1251");
+    assert!(page
+        .get_html()
+        .contains("\u{41f}\u{440}\u{438}\u{432}\u{435}\u{442}"));
+
+    let page = build(
+        "https://choosealicense.com/",
+        PageResponse {
+            content: Some(Bytes::from(
+                "<meta charset=\"Shift_JIS\"><p>\u{65e5}\u{672c}</p>",
+            )),
+            ..Default::default()
+        },
+    );
+
+    assert_eq!(page.get_charset(), "UTF-8");
+    assert_eq!(
+        build(
+            "https://choosealicense.com/",
+            PageResponse {
+                content: Some(Bytes::from_static(b"\xef\xbb\xbf<p>BOM</p>")),
+                ..Default::default()
+            },
+        )
+        .get_html(),
+        "<p>BOM</p>"
+    );
+}
+
+#[cfg(not(feature = "decentralized"))]
+#[test]
+fn test_seo_metadata() {
+    let html = r#"<html><head><title> MIT
+        License </title><meta name="Description" content=" A short license. ">
+        <meta name="robots" content="noindex"><link rel="canonical" href="/licenses/mit/">
+        <link rel="alternate" hreflang="fr" href="/fr/licenses/mit/">
+        <link rel="alternate" type="application/rss+xml" href="/feed.xml"></head>
+        <body><h1>MIT <em>License</em></h1><h1>Permissions</h1></body></html>"#;
+    let page = build(
+        "https://choosealicense.com/licenses/mit/?ref=home",
+        PageResponse {
+            content: Some(Bytes::from(html)),
+            ..Default::default()
+        },
+    );
+
+    assert_eq!(
+        page.get_seo_metadata(),
+        SeoMetadata {
+            title: Some("MIT License".into()),
+            description: Some("A short license.".into()),
+            h1: vec!["MIT License".into(), "Permissions".into()],
+            canonical: Url::parse("https://choosealicense.com/licenses/mit/").ok(),
+            meta_robots: MetaRobots {
+                noindex: true,
+                nofollow: false
+            },
+            hreflang: vec![(
+                "fr".into(),
+                Url::parse("https://choosealicense.com/fr/licenses/mit/").unwrap()
+            )],
+        }
+    );
+    assert_eq!(
+        build("https://choosealicense.com/", Default::default()).get_seo_metadata(),
+        SeoMetadata::default()
+    );
+}
+
+#[cfg(all(feature = "structured_data", not(feature = "decentralized")))]
+#[test]
+fn test_structured_data() {
+    use serde_json::json;
+
+    let html = r#"<html><head><script type="application/ld+json">
+        {"@context": "https://schema.org", "@type": "Organization", "name": "Choose a License"}
+        </script><script type="application/ld+json">{invalid</script></head><body>
+        <div itemscope itemtype="https://schema.org/Product"><span itemprop="name"> MIT
+        License </span><a itemprop="url" href="/licenses/mit/">MIT</a>
+        <span itemprop="keywords">permissive</span><span itemprop="keywords">short</span>
+        <div itemprop="brand" itemscope itemtype="https://schema.org/Brand">
+        <meta itemprop="name" content="OSI"></div></div>
+        <p vocab="https://schema.org/" typeof="Person"><span property="name">Jane</span>
+        <time property="birthDate" datetime="1990-01-01">January 1</time></p></body></html>"#;
+    let page = build(
+        "https://choosealicense.com/",
+        PageResponse {
+            content: Some(Bytes::from(html)),
+            ..Default::default()
+        },
+    );
+    let structured_data = page.get_structured_data();
+
+    assert_eq!(
+        structured_data.json_ld,
+        vec![json!({
+            "@context": "https://schema.org",
+            "@type": "Organization",
+            "name": "Choose a License"
+        })]
+    );
+    assert_eq!(
+        structured_data.microdata,
+        vec![json!({
+            "@type": "https://schema.org/Product",
+            "name": "MIT License",
+            "url": "https://choosealicense.com/licenses/mit/",
+            "keywords": ["permissive", "short"],
+            "brand": {"@type": "https://schema.org/Brand", "name": "OSI"}
+        })]
+    );
+    assert_eq!(
+        structured_data.rdfa,
+        vec![json!({"@type": "Person", "name": "Jane", "birthDate": "1990-01-01"})]
+    );
+    assert_eq!(
+        build("https://choosealicense.com/", Default::default()).get_structured_data(),
+        StructuredData::default()
+    );
+}
+
+#[cfg(all(feature = "open_graph", not(feature = "decentralized")))]
+#[test]
+fn test_social_metadata() {
+    use crate::features::open_graph::{OpenGraph, OpenGraphMedia, TwitterCard};
+
+    let html = r#"<html><head><meta property="og:title" content=" MIT License ">
+        <meta property="og:type" content="article"><meta property="og:url" content="/licenses/mit/">
+        <meta property="og:image" content="/img/mit.png"><meta property="og:image:url" content="/img/mit.png">
+        <meta property="og:image:width" content="1200">
+        <meta property="og:image:alt" content="MIT"><meta property="og:image" content="/img/logo.png">
+        <meta property="og:article:author" content="OSI"><meta property="og:title" content="Other">
+        <meta name="twitter:card" content="summary"><meta name="twitter:site" content="@github">
+        <meta name="twitter:image" content="https://github.com/card.png"></head></html>"#;
+    let page = build(
+        "https://choosealicense.com/",
+        PageResponse {
+            content: Some(Bytes::from(html)),
+            ..Default::default()
+        },
+    );
+    let url = |path: &str| Url::parse(&format!("https://choosealicense.com{}", path)).unwrap();
+
+    assert_eq!(
+        page.get_social_metadata(),
+        SocialMetadata {
+            open_graph: OpenGraph {
+                title: Some("MIT License".into()),
+                kind: Some("article".into()),
+                url: Some(url("/licenses/mit/")),
+                images: vec![
+                    OpenGraphMedia {
+                        width: Some(1200),
+                        alt: Some("MIT".into()),
+                        ..OpenGraphMedia::new(url("/img/mit.png"))
+                    },
+                    OpenGraphMedia::new(url("/img/logo.png"))
+                ],
+                properties: vec![
+                    ("title".into(), "MIT License".into()),
+                    ("type".into(), "article".into()),
+                    ("url".into(), "/licenses/mit/".into()),
+                    ("image".into(), "/img/mit.png".into()),
+                    ("image:url".into(), "/img/mit.png".into()),
+                    ("image:width".into(), "1200".into()),
+                    ("image:alt".into(), "MIT".into()),
+                    ("image".into(), "/img/logo.png".into()),
+                    ("article:author".into(), "OSI".into()),
+                    ("title".into(), "Other".into()),
+                ],
+                ..Default::default()
+            },
+            twitter_card: TwitterCard {
+                card: Some("summary".into()),
+                site: Some("@github".into()),
+                image: Url::parse("https://github.com/card.png").ok(),
+                properties: vec![
+                    ("card".into(), "summary".into()),
+                    ("site".into(), "@github".into()),
+                    ("image".into(), "https://github.com/card.png".into()),
+                ],
+                ..Default::default()
+            },
+        }
+    );
+    assert_eq!(
+        build("https://choosealicense.com/", Default::default()).get_social_metadata(),
+        SocialMetadata::default()
+    );
+}
+
+#[cfg(all(feature = "text_extraction", not(feature = "decentralized")))]
+#[test]
+fn test_article() {
+    let page = build(
+        "https://choosealicense.com/licenses/mit/",
+        PageResponse {
+            content: Some(Bytes::from(
+                r#"<html><head><title>MIT License | Choose a License</title></head><body>
+                <nav><a href="/">Home</a> <a href="/licenses/">Licenses</a></nav>
+                <div class="site-header">Choose an open source license</div>
+                <div id="main-content" class="content">
+                    <h1>MIT License</h1>
+                    <p>A short and simple permissive license with conditions only requiring preservation of copyright and license notices.</p>
+                    <p>Licensed works, modifications, and larger works may be distributed under different terms and without source code.</p>
+                    <div class="share-buttons"><a href="/share">Share this license with your friends</a></div>
+                </div>
+                <aside class="sidebar"><p>Looking for something else, like the licenses of the other projects?</p></aside>
+                <div class="ad-banner"><p>Buy the best hosting for your open source projects today, right now!</p></div>
+                <footer><p>This site is made by the community, with the help of the contributors.</p></footer>
+                </body></html>"#,
+            )),
+            ..Default::default()
+        },
+    );
+    let article = page.get_article();
+
+    assert_eq!(article.title.as_deref(), Some("MIT License"));
+    assert_eq!(
+        article.text,
+        "MIT License\n\nA short and simple permissive license with conditions only requiring preservation of copyright and license notices.\n\nLicensed works, modifications, and larger works may be distributed under different terms and without source code."
+    );
+
+    let page = build(
+        "https://choosealicense.com/",
+        PageResponse {
+            content: Some(Bytes::from(
+                "<title>Choose a License - Open Source</title><body><p>Hi</p></body>",
+            )),
+            ..Default::default()
+        },
+    );
+
+    assert_eq!(
+        page.get_article().title.as_deref(),
+        Some("Choose a License")
+    );
+}
+
+#[cfg(all(feature = "markdown", not(feature = "decentralized")))]
+#[test]
+fn test_markdown() {
+    let page = build(
+        "https://choosealicense.com/licenses/",
+        PageResponse {
+            content: Some(Bytes::from(
+                r#"<html><head><title>Licenses</title><style>p { color: red; }</style></head><body>
+                <h1>Choose a <em>License</em></h1>
+                <p>The <strong>MIT</strong> license is <a href="mit/">short and simple</a>.<br>Read <code>LICENSE</code> first.</p>
+                <script>console.log("skipped");</script>
+                <ul><li>Permissions<ul><li>Commercial use</li></ul></li><li><img src="/assets/img.png" alt="Logo"></li></ul>
+                <ol start="3"><li>Copy</li><li>Paste</li></ol>
+                <pre><code class="language-rust">fn main() {
+    println!("MIT");
+}</code></pre>
+                <blockquote><p>Be kind.</p><p>Be fair.</p></blockquote>
+                <table><tr><th>License</th><th>Use</th></tr><tr><td>MIT</td><td>a | b</td></tr></table>
+                </body></html>"#,
+            )),
+            ..Default::default()
+        },
+    );
+
+    assert_eq!(
+        page.get_markdown(),
+        r#"# Choose a *License*
+
+The **MIT** license is [short and simple](https://choosealicense.com/licenses/mit/).  
+Read `LICENSE` first.
+
+- Permissions
+  - Commercial use
+- ![Logo](https://choosealicense.com/assets/img.png)
+
+3. Copy
+4. Paste
+
+```rust
+fn main() {
+    println!("MIT");
+}
+```
+
+> Be kind.
+>
+> Be fair.
+
+| License | Use |
+| --- | --- |
+| MIT | a \| b |"#
+    );
+}
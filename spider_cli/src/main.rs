@@ -3,18 +3,21 @@ extern crate serde_json;
 extern crate spider;
 
 pub mod options;
+pub mod output;
 
 use clap::Parser;
 use options::{Cli, Commands};
+use output::Output;
 use spider::hashbrown::HashMap;
 use spider::page::get_page_selectors;
 use spider::string_concat::string_concat;
 use spider::string_concat::string_concat_impl;
 use spider::tokio;
+use spider::tokio_stream::StreamExt;
 use spider::url::Url;
 use spider::utils::log;
 use spider::website::Website;
-use std::io::{self, Write};
+use std::io::Write;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
@@ -43,6 +46,8 @@ async fn main() {
         .with_subdomains(cli.subdomains)
         .with_tld(cli.tld)
         .with_user_agent(cli.user_agent.as_deref())
+        .with_limit(cli.limit.unwrap_or_default())
+        .with_depth(cli.depth.unwrap_or_default())
         .with_budget(match cli.budget {
             Some(ref budget) => Some(
                 budget
@@ -70,14 +75,43 @@ async fn main() {
                     website.crawl().await;
 
                     if output_links {
-                        let links: Vec<String> = website
-                            .get_links()
-                            .iter()
-                            .map(|l| l.inner().to_string())
-                            .collect();
-                        io::stdout()
-                            .write_all(format!("{:?}", links).as_bytes())
-                            .unwrap();
+                        let mut output = Output::stdout(cli.format, &["url"]);
+
+                        for link in website.get_links().iter() {
+                            output.write(&link.inner().as_str().into()).unwrap();
+                        }
+
+                        output.finish().unwrap();
+                    }
+                }
+                Some(Commands::LINKS) => {
+                    let mut output = Output::stdout(cli.format, &["url", "status_code"]);
+                    let mut stream = website.stream_crawl(16);
+
+                    while let Some(page) = stream.next().await {
+                        let record = serde_json::json!({
+                            "url": page.get_url(),
+                            "status_code": page.status_code.as_u16(),
+                        });
+
+                        output.write(&record).unwrap();
+                    }
+
+                    output.finish().unwrap();
+                }
+                Some(Commands::SITEMAP { target_destination }) => {
+                    let target_destination =
+                        target_destination.unwrap_or(String::from("./sitemap.xml"));
+
+                    website.crawl().await;
+
+                    match website.export_sitemap(&target_destination) {
+                        Ok(paths) => {
+                            for path in paths {
+                                println!("{}", path.display());
+                            }
+                        }
+                        Err(e) => println!("{:?}", e),
                     }
                 }
                 Some(Commands::DOWNLOAD { target_destination }) => {
@@ -162,7 +196,7 @@ async fn main() {
 
                     website.scrape().await;
 
-                    let mut page_objects: Vec<_> = vec![];
+                    let mut output = Output::stdout(cli.format, &["url", "links", "html"]);
 
                     let selectors = get_page_selectors(&url, cli.subdomains, cli.tld);
 
@@ -191,29 +225,18 @@ async fn main() {
                                             Default::default()
                                         },
                                     });
-                                    page_objects.push(page_json);
+                                    output.write(&page_json).unwrap();
                                 }
                             }
                             _ => (),
                         }
                     }
 
-                    match serde_json::to_string_pretty(&page_objects) {
-                        Ok(j) => {
-                            match io::stdout().write_all(j.as_bytes()) {
-                                Err(e) => {
-                                    println!("{:?}", e)
-                                }
-                                _ => ()
-                            }
-                            io::stdout().write_all(j.as_bytes()).unwrap();
-                        }
-                        Err(e) =>  println!("{:?}", e)
-                    }
+                    output.finish().unwrap();
                 }
                 None => ()
             }
         }
-        _ =>  println!("Invalid website URL passed in. The url should start with http:// or https:// following the website domain ex: https://example.com.")
+        Err(e) => eprintln!("Invalid website configuration: {}", e),
     }
 }
@@ -1,4 +1,5 @@
 use crate::options::sub_command::Commands;
+use crate::output::Format;
 use clap::Parser;
 
 /// program to crawl a website and gather valid web urls.
@@ -38,4 +39,13 @@ pub struct Cli {
     /// Set external domains to group with crawl.
     #[clap(short = 'E', long)]
     pub external_domains: Option<Vec<String>>,
+    /// The max amount of pages to crawl.
+    #[clap(short, long)]
+    pub limit: Option<usize>,
+    /// The max depth of link hops from the url to crawl.
+    #[clap(short, long)]
+    pub depth: Option<usize>,
+    /// The output format of the records: json, jsonl, or csv.
+    #[clap(short, long, value_enum, default_value_t = Format::Json)]
+    pub format: Format,
 }
@@ -20,6 +20,14 @@ pub enum Commands {
         #[clap(long)]
         output_html: bool,
     },
+    /// Crawl the website printing the links visited with their status code as they are crawled.
+    LINKS,
+    /// Crawl the website writing the sitemap of the urls crawled.
+    SITEMAP {
+        /// the path of the sitemap written
+        #[clap(short, long)]
+        target_destination: Option<String>,
+    },
     /// Download html markup to destination.
     DOWNLOAD {
         /// store files at target destination
@@ -0,0 +1,124 @@
+use clap::ValueEnum;
+use serde_json::Value;
+use std::io::{self, Write};
+
+/// The format of the records written to the standard output.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Format {
+    /// A JSON array of the records.
+    #[default]
+    Json,
+    /// A JSON record per line written as the records are crawled.
+    Jsonl,
+    /// A CSV row per record with a header of the columns written as the records are crawled.
+    Csv,
+}
+
+/// The writer of the records of a command in the format.
+pub struct Output<W: Write> {
+    /// The destination of the records.
+    writer: W,
+    /// The format of the records.
+    format: Format,
+    /// The columns of the CSV rows picked from the fields of the records.
+    columns: &'static [&'static str],
+    /// The records written.
+    count: usize,
+}
+
+impl Output<io::Stdout> {
+    /// Write the records with the columns to the standard output.
+    pub fn stdout(format: Format, columns: &'static [&'static str]) -> Self {
+        Output::new(io::stdout(), format, columns)
+    }
+}
+
+impl<W: Write> Output<W> {
+    /// Write the records with the columns to the writer.
+    pub fn new(writer: W, format: Format, columns: &'static [&'static str]) -> Self {
+        Self {
+            writer,
+            format,
+            columns,
+            count: 0,
+        }
+    }
+
+    /// Write the record. The records without fields fill the first column.
+    pub fn write(&mut self, record: &Value) -> io::Result<()> {
+        match self.format {
+            Format::Json => {
+                let separator = if self.count == 0 { "[\n" } else { ",\n" };
+
+                self.writer.write_all(separator.as_bytes())?;
+                self.writer.write_all(record.to_string().as_bytes())?;
+            }
+            Format::Jsonl => {
+                self.writer.write_all(record.to_string().as_bytes())?;
+                self.writer.write_all(b"\n")?;
+            }
+            Format::Csv => {
+                if self.count == 0 {
+                    self.write_header()?;
+                }
+
+                let row: Vec<String> = match record {
+                    Value::Object(fields) => self
+                        .columns
+                        .iter()
+                        .map(|column| get_csv_cell(fields.get(*column).unwrap_or(&Value::Null)))
+                        .collect(),
+                    _ => vec![get_csv_cell(record)],
+                };
+
+                self.writer.write_all(row.join(",").as_bytes())?;
+                self.writer.write_all(b"\n")?;
+            }
+        }
+
+        self.count += 1;
+        self.writer.flush()
+    }
+
+    /// End the records written.
+    pub fn finish(mut self) -> io::Result<()> {
+        match self.format {
+            Format::Json if self.count == 0 => self.writer.write_all(b"[]\n")?,
+            Format::Json => self.writer.write_all(b"\n]\n")?,
+            Format::Csv if self.count == 0 => self.write_header()?,
+            _ => (),
+        }
+
+        self.writer.flush()
+    }
+
+    /// Write the header of the columns.
+    fn write_header(&mut self) -> io::Result<()> {
+        self.writer.write_all(self.columns.join(",").as_bytes())?;
+        self.writer.write_all(b"\n")
+    }
+}
+
+/// Get the CSV cell of the value quoted when it has a comma, a quote, or a line break. The arrays
+/// are joined by spaces.
+fn get_csv_cell(value: &Value) -> String {
+    let cell = match value {
+        Value::Null => Default::default(),
+        Value::String(value) => value.clone(),
+        Value::Array(values) => values
+            .iter()
+            .map(|value| match value {
+                Value::String(value) => value.clone(),
+                _ => value.to_string(),
+            })
+            .collect::<Vec<_>>()
+            .join(" "),
+        _ => value.to_string(),
+    };
+
+    if cell.contains(&[',', '"', '\n', '\r'][..]) {
+        format!("\"{}\"", cell.replace('"', "\"\""))
+    } else {
+        cell
+    }
+}
@@ -1,11 +1,15 @@
 use std::convert::Infallible;
 
-use spider::{tokio, utils, website::Website};
-use warp::{path::FullPath, Filter};
+use spider::{tokio, utils};
+use warp::Filter;
+#[cfg(not(feature = "coordinator"))]
+use {spider::website::Website, warp::path::FullPath};
 
+#[cfg(not(feature = "coordinator"))]
 #[macro_use]
 extern crate lazy_static;
 
+#[cfg(not(feature = "coordinator"))]
 lazy_static! {
     /// top level request client to re-use
     static ref CLIENT: spider::reqwest::Client = {
@@ -17,7 +21,7 @@ lazy_static! {
 }
 
 /// forward request to get resources
-#[cfg(not(feature = "scrape"))]
+#[cfg(all(not(feature = "scrape"), not(feature = "coordinator")))]
 async fn forward(
     path: FullPath,
     host: String,
@@ -74,7 +78,10 @@ async fn forward(
 }
 
 /// forward request to get links resources
-#[cfg(not(all(not(feature = "scrape"), not(feature = "full_resources"))))]
+#[cfg(all(
+    not(all(not(feature = "scrape"), not(feature = "full_resources"))),
+    not(feature = "coordinator")
+))]
 async fn scrape(path: FullPath, host: String) -> Result<impl warp::Reply, Infallible> {
     use spider::string_concat::{string_concat, string_concat_impl};
 
@@ -97,11 +104,38 @@ async fn scrape(path: FullPath, host: String) -> Result<impl warp::Reply, Infall
     Ok(data.content.unwrap_or_default().to_vec())
 }
 
+/// lease the links of the crawl to the workers
+#[cfg(feature = "coordinator")]
+async fn lease(
+    coordinator: std::sync::Arc<spider::features::coordinator::Coordinator>,
+    request: spider::features::coordinator::LeaseRequest,
+) -> Result<impl warp::Reply, Infallible> {
+    Ok(warp::reply::json(&coordinator.lease(&request)))
+}
+
+/// report the pages of a lease of the crawl
+#[cfg(feature = "coordinator")]
+async fn report(
+    coordinator: std::sync::Arc<spider::features::coordinator::Coordinator>,
+    report: spider::features::coordinator::Report,
+) -> Result<impl warp::Reply, Infallible> {
+    Ok(warp::reply::json(&coordinator.report(report)))
+}
+
+/// status of the crawl
+#[cfg(feature = "coordinator")]
+async fn status(
+    coordinator: std::sync::Arc<spider::features::coordinator::Coordinator>,
+) -> Result<impl warp::Reply, Infallible> {
+    Ok(warp::reply::json(&coordinator.get_status()))
+}
+
 #[tokio::main]
 #[cfg(all(
     not(feature = "scrape"),
     not(feature = "full_resources"),
-    not(feature = "tls")
+    not(feature = "tls"),
+    not(feature = "coordinator")
 ))]
 async fn main() {
     env_logger::init();
@@ -126,7 +160,7 @@ async fn main() {
 }
 
 #[tokio::main]
-#[cfg(all(feature = "scrape", not(feature = "tls"),))]
+#[cfg(all(feature = "scrape", not(feature = "tls"), not(feature = "coordinator")))]
 async fn main() {
     env_logger::init();
     let host = warp::header::<String>("host");
@@ -146,6 +180,7 @@ async fn main() {
     feature = "full_resources",
     not(feature = "tls"),
     not(feature = "scrape"),
+    not(feature = "coordinator")
 ))]
 async fn main() {
     env_logger::init();
@@ -188,7 +223,8 @@ async fn main() {
 #[cfg(all(
     not(feature = "scrape"),
     not(feature = "full_resources"),
-    feature = "tls"
+    feature = "tls",
+    not(feature = "coordinator")
 ))]
 async fn main() {
     env_logger::init();
@@ -223,7 +259,7 @@ async fn main() {
 }
 
 #[tokio::main]
-#[cfg(all(feature = "scrape", feature = "tls"))]
+#[cfg(all(feature = "scrape", feature = "tls", not(feature = "coordinator")))]
 async fn main() {
     env_logger::init();
     let host = warp::header::<String>("host");
@@ -249,7 +285,12 @@ async fn main() {
 }
 
 #[tokio::main]
-#[cfg(all(not(feature = "scrape"), feature = "full_resources", feature = "tls"))]
+#[cfg(all(
+    not(feature = "scrape"),
+    feature = "full_resources",
+    feature = "tls",
+    not(feature = "coordinator")
+))]
 async fn main() {
     env_logger::init();
     let host = warp::header::<String>("host");
@@ -295,3 +336,60 @@ async fn main() {
         .run(([0, 0, 0, 0], port))
         .await;
 }
+
+// distributed crawls
+
+#[tokio::main]
+#[cfg(feature = "coordinator")]
+async fn main() {
+    use spider::features::coordinator::{Coordinator, CrawlBudget};
+
+    env_logger::init();
+
+    let url = std::env::var("SPIDER_COORDINATOR_URL").expect("SPIDER_COORDINATOR_URL is required");
+    let budget = CrawlBudget {
+        max_pages: std::env::var("SPIDER_COORDINATOR_MAX_PAGES")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or_default(),
+        max_depth: std::env::var("SPIDER_COORDINATOR_MAX_DEPTH")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or_default(),
+        ..Default::default()
+    };
+    let coordinator = std::sync::Arc::new(Coordinator::new(&url, budget));
+    let with_coordinator = warp::any().map(move || coordinator.clone());
+
+    let lease_route = warp::post()
+        .and(warp::path("lease"))
+        .and(warp::path::end())
+        .and(with_coordinator.clone())
+        .and(warp::body::json())
+        .and_then(lease);
+    let report_route = warp::post()
+        .and(warp::path("report"))
+        .and(warp::path::end())
+        .and(with_coordinator.clone())
+        .and(warp::body::json())
+        .and_then(report);
+    let status_route = warp::get()
+        .and(warp::path("status"))
+        .and(warp::path::end())
+        .and(with_coordinator)
+        .and_then(status);
+
+    let routes = lease_route.or(report_route).or(status_route).boxed();
+
+    let port: u16 = std::env::var("SPIDER_COORDINATOR_PORT")
+        .unwrap_or_else(|_| "3032".into())
+        .parse()
+        .unwrap_or_else(|_| 3032);
+
+    utils::log(
+        "Spider_Worker coordinator starting at 0.0.0.0:",
+        &port.to_string(),
+    );
+
+    warp::serve(routes).run(([0, 0, 0, 0], port)).await;
+}